@@ -0,0 +1,32 @@
+export interface MatchResult {
+  word: string
+  distance: number
+  similarity: number
+  is_exact: boolean
+}
+
+const decoder = new TextDecoder()
+
+// Decode the buffer returned by `find_matches_packed`: a u32 count, then per
+// match a u32 word length, the UTF-8 word, a u32 distance and an f64
+// similarity, all little-endian
+export function unpackResults(bytes: Uint8Array): MatchResult[] {
+  const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength)
+  const count = view.getUint32(0, true)
+  const results: MatchResult[] = []
+  let offset = 4
+
+  for (let i = 0; i < count; i++) {
+    const length = view.getUint32(offset, true)
+    offset += 4
+    const word = decoder.decode(bytes.subarray(offset, offset + length))
+    offset += length
+    const distance = view.getUint32(offset, true)
+    offset += 4
+    const similarity = view.getFloat64(offset, true)
+    offset += 8
+    results.push({ word, distance, similarity, is_exact: distance === 0 })
+  }
+
+  return results
+}
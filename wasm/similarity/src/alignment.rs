@@ -0,0 +1,436 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::{levenshtein_matrix, QuizPair};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DistanceMatrix {
+    pub distance: usize,
+    /// `(len1 + 1) x (len2 + 1)` DP table, rows following `s1`
+    pub matrix: Vec<Vec<usize>>,
+}
+
+fn distance_with_matrix(s1: &str, s2: &str) -> DistanceMatrix {
+    let matrix = levenshtein_matrix(s1, s2);
+    let distance = matrix[matrix.len() - 1][matrix[0].len() - 1];
+    DistanceMatrix { distance, matrix }
+}
+
+/// Calculate the Levenshtein distance together with the full DP table as
+/// `{ distance, matrix }`, for showing how the table fills in
+#[wasm_bindgen]
+pub fn calculate_distance_with_matrix(s1: &str, s2: &str) -> Result<JsValue, JsValue> {
+    serde_wasm_bindgen::to_value(&distance_with_matrix(s1, s2))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+/// One column of an optimal alignment between two strings
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum EditStep {
+    Match(char),
+    Substitute(char, char),
+    /// Char of `s1` missing from `s2`
+    Delete(char),
+    /// Char of `s2` missing from `s1`
+    Insert(char),
+}
+
+/// Trace one optimal Levenshtein path back through the DP table, preferring
+/// matches and substitutions over gaps. Steps are in string order.
+pub(crate) fn edit_steps(s1: &str, s2: &str) -> Vec<EditStep> {
+    let a: Vec<char> = s1.chars().collect();
+    let b: Vec<char> = s2.chars().collect();
+    let matrix = levenshtein_matrix(s1, s2);
+
+    let (mut i, mut j) = (a.len(), b.len());
+    let mut steps = Vec::with_capacity(i.max(j));
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            if matrix[i][j] == matrix[i - 1][j - 1] + cost {
+                steps.push(if cost == 0 {
+                    EditStep::Match(a[i - 1])
+                } else {
+                    EditStep::Substitute(a[i - 1], b[j - 1])
+                });
+                i -= 1;
+                j -= 1;
+                continue;
+            }
+        }
+        if i > 0 && matrix[i][j] == matrix[i - 1][j] + 1 {
+            steps.push(EditStep::Delete(a[i - 1]));
+            i -= 1;
+        } else {
+            steps.push(EditStep::Insert(b[j - 1]));
+            j -= 1;
+        }
+    }
+
+    steps.reverse();
+    steps
+}
+
+/// Gap character in aligned strings
+const GAP: char = '-';
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Alignment {
+    pub aligned_s1: String,
+    pub aligned_s2: String,
+    /// `|` under matching columns, a space under substitutions and gaps
+    pub markers: String,
+}
+
+fn align(s1: &str, s2: &str) -> Alignment {
+    let mut alignment = Alignment {
+        aligned_s1: String::new(),
+        aligned_s2: String::new(),
+        markers: String::new(),
+    };
+    for step in edit_steps(s1, s2) {
+        let (top, bottom, marker) = match step {
+            EditStep::Match(c) => (c, c, '|'),
+            EditStep::Substitute(x, y) => (x, y, ' '),
+            EditStep::Delete(x) => (x, GAP, ' '),
+            EditStep::Insert(y) => (GAP, y, ' '),
+        };
+        alignment.aligned_s1.push(top);
+        alignment.aligned_s2.push(bottom);
+        alignment.markers.push(marker);
+    }
+    alignment
+}
+
+/// Global alignment of two strings as `{ aligned_s1, aligned_s2, markers }`,
+/// with `-` for gaps and `|` marking matching columns
+#[wasm_bindgen]
+pub fn best_alignment(s1: &str, s2: &str) -> JsValue {
+    serde_wasm_bindgen::to_value(&align(s1, s2)).unwrap_or(JsValue::NULL)
+}
+
+/// Byte ranges `(start1, end1, start2, end2)` of the part where two strings
+/// differ, after removing their common prefix and common suffix. Identical
+/// strings give empty ranges at their end.
+pub(crate) fn minimal_distinguishing_substring(s1: &str, s2: &str) -> (usize, usize, usize, usize) {
+    let prefix: usize = s1
+        .chars()
+        .zip(s2.chars())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum();
+
+    let (rest1, rest2) = (&s1[prefix..], &s2[prefix..]);
+    let suffix: usize = rest1
+        .chars()
+        .rev()
+        .zip(rest2.chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum();
+
+    (prefix, s1.len() - suffix, prefix, s2.len() - suffix)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DistinguishingPart {
+    pub s1_part: String,
+    pub s2_part: String,
+    /// Byte offset of the differing part (the same in both strings)
+    pub position: usize,
+}
+
+fn distinguishing_part(s1: &str, s2: &str) -> DistinguishingPart {
+    let (start1, end1, start2, end2) = minimal_distinguishing_substring(s1, s2);
+    DistinguishingPart {
+        s1_part: s1[start1..end1].to_string(),
+        s2_part: s2[start2..end2].to_string(),
+        position: start1,
+    }
+}
+
+/// The part that tells two similar words apart as `{ s1_part, s2_part, position }`
+#[wasm_bindgen]
+pub fn find_distinguishing_part(s1: &str, s2: &str) -> JsValue {
+    serde_wasm_bindgen::to_value(&distinguishing_part(s1, s2)).unwrap_or(JsValue::NULL)
+}
+
+/// Longest common subsequence of two strings (by char), reconstructed by
+/// backtracking through the LCS length table. Ties prefer skipping a char
+/// of `s1`, so the result is deterministic.
+pub(crate) fn lcs_string(s1: &str, s2: &str) -> String {
+    let a: Vec<char> = s1.chars().collect();
+    let b: Vec<char> = s2.chars().collect();
+
+    let mut lengths = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            lengths[i][j] = if a[i - 1] == b[j - 1] {
+                lengths[i - 1][j - 1] + 1
+            } else {
+                lengths[i - 1][j].max(lengths[i][j - 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (a.len(), b.len());
+    let mut lcs = Vec::with_capacity(lengths[i][j]);
+    while i > 0 && j > 0 {
+        if a[i - 1] == b[j - 1] {
+            lcs.push(a[i - 1]);
+            i -= 1;
+            j -= 1;
+        } else if lengths[i - 1][j] >= lengths[i][j - 1] {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+    lcs.iter().rev().collect()
+}
+
+/// The characters two strings have in common, in order (their longest
+/// common subsequence)
+#[wasm_bindgen]
+pub fn get_lcs_string(s1: &str, s2: &str) -> String {
+    lcs_string(s1, s2)
+}
+
+/// Number of leading chars of `needle` found in order within `haystack`
+/// (greedy, so other chars may be interspersed).
+fn matched_subsequence_chars(needle: &str, haystack: &str) -> usize {
+    let mut rest = haystack.chars();
+    needle.chars().take_while(|&c| rest.any(|h| h == c)).count()
+}
+
+/// Whether all chars of `needle` appear in `haystack` in the same order
+#[wasm_bindgen]
+pub fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    matched_subsequence_chars(needle, haystack) == needle.chars().count()
+}
+
+/// Chars of `needle` matched in order within `haystack`, divided by the
+/// haystack length. Two empty strings score 1.0, an empty haystack otherwise 0.0.
+#[wasm_bindgen]
+pub fn subsequence_score(needle: &str, haystack: &str) -> f64 {
+    let haystack_len = haystack.chars().count();
+    if haystack_len == 0 {
+        return if needle.is_empty() { 1.0 } else { 0.0 };
+    }
+    matched_subsequence_chars(needle, haystack) as f64 / haystack_len as f64
+}
+
+/// Number of confusion pairs kept by `character_confusion_report`
+const TOP_CONFUSIONS: usize = 5;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CharConfusion {
+    /// Character the learner should have typed
+    pub from: char,
+    /// Character they typed instead
+    pub to: char,
+    pub count: usize,
+}
+
+/// Count the substitutions in each attempt's edit path and keep the most
+/// frequent pairs, by count descending and then alphabetically.
+fn confusion_report(attempts: &[QuizPair]) -> Vec<CharConfusion> {
+    let mut counts: HashMap<(char, char), usize> = HashMap::new();
+    for attempt in attempts {
+        for step in edit_steps(&attempt.correct, &attempt.user_answer) {
+            if let EditStep::Substitute(from, to) = step {
+                *counts.entry((from, to)).or_default() += 1;
+            }
+        }
+    }
+
+    let mut report: Vec<CharConfusion> = counts
+        .into_iter()
+        .map(|((from, to), count)| CharConfusion { from, to, count })
+        .collect();
+    report.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then(a.from.cmp(&b.from))
+            .then(a.to.cmp(&b.to))
+    });
+    report.truncate(TOP_CONFUSIONS);
+    report
+}
+
+/// The character substitutions a learner makes most often across an array
+/// of `{ correct, user_answer }` attempts, as `{ from, to, count }` entries
+#[wasm_bindgen]
+pub fn character_confusion_report(attempts: JsValue) -> Result<JsValue, JsValue> {
+    let attempts_vec: Vec<QuizPair> = serde_wasm_bindgen::from_value(attempts)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse attempts: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&confusion_report(&attempts_vec))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate_distance;
+
+    #[test]
+    fn test_distance_with_matrix() {
+        for (s1, s2) in [
+            ("kitten", "sitting"),
+            ("", "abc"),
+            ("abc", ""),
+            ("", ""),
+            ("héllo", "hello"),
+        ] {
+            let result = distance_with_matrix(s1, s2);
+            let (len1, len2) = (s1.chars().count(), s2.chars().count());
+
+            assert_eq!(result.matrix.len(), len1 + 1);
+            assert!(result.matrix.iter().all(|row| row.len() == len2 + 1));
+            assert!((0..=len2).all(|j| result.matrix[0][j] == j));
+            assert!((0..=len1).all(|i| result.matrix[i][0] == i));
+            assert_eq!(result.matrix[len1][len2], result.distance);
+            assert_eq!(result.distance, calculate_distance(s1, s2));
+        }
+    }
+
+    #[test]
+    fn test_edit_steps() {
+        let steps = edit_steps("cat", "cart");
+        assert_eq!(
+            steps,
+            vec![
+                EditStep::Match('c'),
+                EditStep::Match('a'),
+                EditStep::Insert('r'),
+                EditStep::Match('t'),
+            ]
+        );
+        let edits = edit_steps("kitten", "sitting")
+            .into_iter()
+            .filter(|s| !matches!(s, EditStep::Match(_)))
+            .count();
+        assert_eq!(edits, calculate_distance("kitten", "sitting"));
+        assert!(edit_steps("", "").is_empty());
+    }
+
+    #[test]
+    fn test_align() {
+        let alignment = align("kitten", "sitting");
+        let columns = alignment.aligned_s1.chars().count();
+        assert_eq!(alignment.aligned_s2.chars().count(), columns);
+        assert_eq!(alignment.markers.chars().count(), columns);
+        assert_eq!(alignment.aligned_s1.replace(GAP, ""), "kitten");
+        assert_eq!(alignment.aligned_s2.replace(GAP, ""), "sitting");
+
+        let alignment = align("cat", "cart");
+        assert_eq!(alignment.aligned_s1, "ca-t");
+        assert_eq!(alignment.aligned_s2, "cart");
+        assert_eq!(alignment.markers, "|| |");
+    }
+
+    #[test]
+    fn test_align_identical() {
+        let alignment = align("house", "house");
+        assert_eq!(alignment.markers, "|||||");
+        assert_eq!(alignment.aligned_s1, alignment.aligned_s2);
+    }
+
+    #[test]
+    fn test_minimal_distinguishing_substring() {
+        assert_eq!(
+            minimal_distinguishing_substring("affect", "effect"),
+            (0, 1, 0, 1)
+        );
+        assert_eq!(
+            minimal_distinguishing_substring("cat", "cart"),
+            (2, 2, 2, 3)
+        );
+        assert_eq!(minimal_distinguishing_substring("aaa", "aa"), (2, 3, 2, 2));
+        assert_eq!(
+            minimal_distinguishing_substring("café", "cafe"),
+            (3, 5, 3, 4)
+        );
+    }
+
+    #[test]
+    fn test_distinguishing_part() {
+        let part = distinguishing_part("affect", "effect");
+        assert_eq!(
+            (part.s1_part.as_str(), part.s2_part.as_str(), part.position),
+            ("a", "e", 0)
+        );
+
+        let part = distinguishing_part("principal", "principle");
+        assert_eq!((part.s1_part.as_str(), part.s2_part.as_str()), ("al", "le"));
+
+        let part = distinguishing_part("same", "same");
+        assert!(part.s1_part.is_empty() && part.s2_part.is_empty());
+    }
+
+    #[test]
+    fn test_lcs_string() {
+        let lcs = lcs_string("ABCBDAB", "BDCAB");
+        assert_eq!(lcs.chars().count(), 4);
+        assert!(is_subsequence(&lcs, "ABCBDAB"));
+        assert!(is_subsequence(&lcs, "BDCAB"));
+
+        assert_eq!(lcs_string("receive", "receive"), "receive");
+        assert_eq!(lcs_string("abc", "xyz"), "");
+        assert_eq!(lcs_string("", "abc"), "");
+        assert_eq!(lcs_string("naïve", "naive"), "nave");
+    }
+
+    #[test]
+    fn test_is_subsequence() {
+        assert!(is_subsequence("ace", "abcde"));
+        assert!(!is_subsequence("aec", "abcde"));
+        assert!(is_subsequence("", "abc"));
+        assert!(!is_subsequence("abc", ""));
+    }
+
+    #[test]
+    fn test_subsequence_score() {
+        assert_eq!(subsequence_score("ace", "abcde"), 3.0 / 5.0);
+        assert_eq!(subsequence_score("abcde", "abcde"), 1.0);
+        assert_eq!(subsequence_score("aec", "abcde"), 2.0 / 5.0);
+        assert_eq!(subsequence_score("", ""), 1.0);
+        assert_eq!(subsequence_score("a", ""), 0.0);
+    }
+
+    fn attempt(correct: &str, user_answer: &str) -> QuizPair {
+        QuizPair {
+            correct: correct.to_string(),
+            user_answer: user_answer.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_confusion_report() {
+        let mut attempts: Vec<QuizPair> = (0..10).map(|_| attempt("receive", "recieve")).collect();
+        attempts.push(attempt("their", "there"));
+        attempts.push(attempt("cat", "cat"));
+        let report = confusion_report(&attempts);
+
+        // "receive" -> "recieve" is two substitutions: e->i and i->e
+        assert_eq!(
+            (report[0].from, report[0].to, report[0].count),
+            ('e', 'i', 10)
+        );
+        assert_eq!(
+            (report[1].from, report[1].to, report[1].count),
+            ('i', 'e', 10)
+        );
+        assert!(report.windows(2).all(|w| w[0].count >= w[1].count));
+        assert!(report.len() <= TOP_CONFUSIONS);
+    }
+
+    #[test]
+    fn test_confusion_report_empty() {
+        assert!(confusion_report(&[]).is_empty());
+        assert!(confusion_report(&[attempt("cat", "cart")]).is_empty());
+    }
+}
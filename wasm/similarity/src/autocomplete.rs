@@ -0,0 +1,98 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{levenshtein_matrix, similarity_score, MatchResult};
+
+/// Most edits allowed between the typed text and the start of a suggestion
+const MAX_AUTOCOMPLETE_DISTANCE: usize = 2;
+
+/// Edit distance between `partial` and the closest prefix of `word`, so
+/// "jel" is one edit from "hello" even though the full words differ more.
+pub(crate) fn prefix_edit_distance(partial: &str, word: &str) -> usize {
+    let matrix = levenshtein_matrix(partial, word);
+    matrix[matrix.len() - 1].iter().copied().min().unwrap_or(0)
+}
+
+/// Suggestions for a partially typed word: words starting with `partial`
+/// come first (distance 0), then words whose beginning is within two edits
+/// of it. Each group is sorted by similarity to `partial` (descending, then
+/// alphabetically) and at most `max_results` are returned.
+fn autocomplete(partial: &str, vocabulary: &[String], max_results: usize) -> Vec<MatchResult> {
+    let mut prefixed = Vec::new();
+    let mut fuzzy = Vec::new();
+    for word in vocabulary {
+        let distance = if word.starts_with(partial) {
+            0
+        } else {
+            prefix_edit_distance(partial, word)
+        };
+        if distance > MAX_AUTOCOMPLETE_DISTANCE {
+            continue;
+        }
+        let m = MatchResult {
+            word: word.clone(),
+            distance,
+            similarity: similarity_score(partial, word),
+            is_exact: partial == word,
+        };
+        if distance == 0 {
+            prefixed.push(m);
+        } else {
+            fuzzy.push(m);
+        }
+    }
+
+    prefixed.sort();
+    fuzzy.sort();
+    prefixed.extend(fuzzy);
+    prefixed.truncate(max_results);
+    prefixed
+}
+
+/// Complete the text of a search box from the vocabulary: prefix matches
+/// first, then fuzzy matches, at most `max_results` in total
+#[wasm_bindgen]
+pub fn fuzzy_autocomplete(
+    partial: &str,
+    vocabulary: JsValue,
+    max_results: usize,
+) -> Result<JsValue, JsValue> {
+    let vocab_vec: Vec<String> = serde_wasm_bindgen::from_value(vocabulary)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse vocabulary: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&autocomplete(partial, &vocab_vec, max_results))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words;
+
+    #[test]
+    fn test_prefix_edit_distance() {
+        assert_eq!(prefix_edit_distance("hel", "hello"), 0);
+        assert_eq!(prefix_edit_distance("jel", "hello"), 1);
+        assert_eq!(prefix_edit_distance("", "hello"), 0);
+        assert_eq!(prefix_edit_distance("xyz", "hello"), 3);
+    }
+
+    #[test]
+    fn test_autocomplete_order() {
+        let vocab = words(&["jello", "yellow", "help", "hello", "world"]);
+        let results = autocomplete("hel", &vocab, 10);
+        let suggestions: Vec<&str> = results.iter().map(|m| m.word.as_str()).collect();
+
+        assert_eq!(&suggestions[..2], &["help", "hello"]);
+        let hello = suggestions.iter().position(|w| *w == "hello").unwrap();
+        let jello = suggestions.iter().position(|w| *w == "jello").unwrap();
+        assert!(hello < jello);
+        assert!(!suggestions.contains(&"world"));
+    }
+
+    #[test]
+    fn test_autocomplete_max_results() {
+        let vocab = words(&["hello", "help", "helmet", "jello"]);
+        assert_eq!(autocomplete("hel", &vocab, 2).len(), 2);
+        assert!(autocomplete("hel", &vocab, 0).is_empty());
+    }
+}
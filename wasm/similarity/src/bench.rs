@@ -0,0 +1,54 @@
+//! Timing helper so the JS test suite can catch performance regressions in
+//! the WASM build. `run_benchmark` is only exported with the `bench` feature.
+
+use std::hint::black_box;
+
+#[cfg(feature = "bench")]
+use wasm_bindgen::prelude::*;
+
+use crate::calculate_distance;
+
+/// Milliseconds taken by `n` calls of `calculate_distance("kitten", "sitting")`,
+/// reading the time from `now` (in milliseconds). No calls take no time.
+fn time_calls(n: usize, now: impl Fn() -> f64) -> f64 {
+    if n == 0 {
+        return 0.0;
+    }
+
+    let start = now();
+    for _ in 0..n {
+        black_box(calculate_distance(
+            black_box("kitten"),
+            black_box("sitting"),
+        ));
+    }
+    now() - start
+}
+
+/// Run `n` distance calculations and return the wall-clock time in milliseconds
+#[cfg(feature = "bench")]
+#[wasm_bindgen]
+pub fn run_benchmark(n: usize) -> f64 {
+    time_calls(n, js_sys::Date::now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn elapsed_ms(origin: Instant) -> impl Fn() -> f64 {
+        move || origin.elapsed().as_secs_f64() * 1000.0
+    }
+
+    #[test]
+    fn test_time_calls_positive() {
+        let elapsed = time_calls(10_000, elapsed_ms(Instant::now()));
+        assert!(elapsed > 0.0);
+    }
+
+    #[test]
+    fn test_time_calls_zero() {
+        assert_eq!(time_calls(0, elapsed_ms(Instant::now())), 0.0);
+    }
+}
@@ -0,0 +1,225 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+
+use wasm_bindgen::prelude::*;
+
+use crate::{levenshtein_distance, similarity_from_distance, CompareOptions, MatchResult};
+
+/// Maximum number of word pairs kept by the shared distance cache
+const MAX_CACHED_PAIRS: usize = 10_000;
+
+/// Bounded memo of Levenshtein distances keyed by unordered word pair.
+/// Once full, the least recently used pair is evicted.
+pub struct DistanceCache {
+    capacity: usize,
+    /// Pair -> (distance, last use)
+    entries: HashMap<(String, String), (usize, u64)>,
+    /// Last use -> pair, oldest first
+    recency: BTreeMap<u64, (String, String)>,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl DistanceCache {
+    pub fn new(capacity: usize) -> Self {
+        DistanceCache {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            clock: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Lookups answered from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Lookups that had to compute the distance
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    fn get(&mut self, key: &(String, String)) -> Option<usize> {
+        self.clock += 1;
+        let (distance, last_used) = self.entries.get_mut(key)?;
+        if let Some(pair) = self.recency.remove(last_used) {
+            self.recency.insert(self.clock, pair);
+        }
+        *last_used = self.clock;
+        Some(*distance)
+    }
+
+    fn insert(&mut self, key: (String, String), distance: usize) {
+        if self.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = self.recency.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.clock += 1;
+        self.recency.insert(self.clock, key.clone());
+        self.entries.insert(key, (distance, self.clock));
+    }
+}
+
+impl Default for DistanceCache {
+    fn default() -> Self {
+        DistanceCache::new(MAX_CACHED_PAIRS)
+    }
+}
+
+/// Levenshtein distance, reusing a previously computed value when available.
+/// The key is order-independent since the distance is symmetric.
+pub(crate) fn levenshtein_memoized(s1: &str, s2: &str, cache: &mut DistanceCache) -> usize {
+    let key = if s1 <= s2 {
+        (s1.to_string(), s2.to_string())
+    } else {
+        (s2.to_string(), s1.to_string())
+    };
+
+    if let Some(distance) = cache.get(&key) {
+        cache.hits += 1;
+        return distance;
+    }
+    cache.misses += 1;
+    let distance = levenshtein_distance(s1, s2);
+    cache.insert(key, distance);
+    distance
+}
+
+thread_local! {
+    static DISTANCE_CACHE: RefCell<DistanceCache> = RefCell::new(DistanceCache::default());
+}
+
+/// Same as `match_words`, but distances come from the shared thread-local cache.
+fn cached_match_words(query: &str, words: &[String], options: &CompareOptions) -> Vec<MatchResult> {
+    let mut matches: Vec<MatchResult> = DISTANCE_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        words
+            .iter()
+            .map(|word| {
+                let distance = levenshtein_memoized(query, word, &mut cache);
+                MatchResult {
+                    word: word.clone(),
+                    distance,
+                    similarity: similarity_from_distance(distance, query, word),
+                    is_exact: query == word,
+                }
+            })
+            .filter(|m| m.similarity >= options.threshold)
+            .filter(|m| !(options.exclude_exact && m.distance == 0))
+            .collect()
+    });
+
+    matches.sort();
+    matches
+}
+
+/// Find fuzzy matches like `find_matches`, memoizing distances across calls
+#[wasm_bindgen]
+pub fn find_matches_cached(
+    query: &str,
+    words: JsValue,
+    threshold: f64,
+) -> Result<JsValue, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    let options = CompareOptions {
+        threshold,
+        ..CompareOptions::default()
+    };
+    let matches = cached_match_words(query, &words_vec, &options);
+
+    serde_wasm_bindgen::to_value(&matches)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{match_words, words};
+
+    #[test]
+    fn test_levenshtein_memoized_symmetric_key() {
+        let mut cache = DistanceCache::new(10);
+        assert_eq!(levenshtein_memoized("kitten", "sitting", &mut cache), 3);
+        assert_eq!(levenshtein_memoized("sitting", "kitten", &mut cache), 3);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 1);
+    }
+
+    #[test]
+    fn test_cache_lru_eviction() {
+        let mut cache = DistanceCache::new(2);
+        levenshtein_memoized("a", "b", &mut cache);
+        levenshtein_memoized("c", "d", &mut cache);
+        // Touch ("a", "b") so ("c", "d") becomes the least recently used
+        levenshtein_memoized("a", "b", &mut cache);
+        levenshtein_memoized("e", "f", &mut cache);
+        assert_eq!(cache.len(), 2);
+
+        let hits = cache.hits();
+        levenshtein_memoized("a", "b", &mut cache);
+        assert_eq!(cache.hits(), hits + 1);
+        levenshtein_memoized("c", "d", &mut cache);
+        assert_eq!(cache.hits(), hits + 1);
+    }
+
+    #[test]
+    fn test_cache_is_bounded() {
+        let mut cache = DistanceCache::default();
+        for i in 0..MAX_CACHED_PAIRS + 500 {
+            levenshtein_memoized("query", &format!("word{}", i), &mut cache);
+        }
+        assert_eq!(cache.len(), MAX_CACHED_PAIRS);
+    }
+
+    #[test]
+    fn test_cached_match_words() {
+        let vocab = words(&["cat", "cart", "bat", "dog"]);
+        let options = CompareOptions::default();
+
+        let first = cached_match_words("cat", &vocab, &options);
+        let counts = || DISTANCE_CACHE.with(|c| (c.borrow().hits(), c.borrow().misses()));
+        let (hits_before, misses_before) = counts();
+        let second = cached_match_words("cat", &vocab, &options);
+        let (hits_after, misses_after) = counts();
+
+        assert_eq!(hits_after, hits_before + vocab.len() as u64);
+        assert_eq!(misses_after, misses_before);
+        let plain = match_words("cat", &vocab, &options);
+        let scores = |m: &[MatchResult]| {
+            m.iter()
+                .map(|r| (r.word.clone(), r.distance, r.similarity))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(scores(&first), scores(&plain));
+        assert_eq!(scores(&second), scores(&plain));
+    }
+
+    #[test]
+    fn test_cached_match_words_uses_memoized_distance() {
+        // Plant a wrong distance: a hit must report it rather than rerun the DP
+        DISTANCE_CACHE.with(|c| c.borrow_mut().insert(("cat".into(), "dog".into()), 1));
+        let options = CompareOptions {
+            threshold: 0.0,
+            ..CompareOptions::default()
+        };
+        let matches = cached_match_words("cat", &words(&["dog"]), &options);
+        assert_eq!(matches[0].distance, 1);
+        assert!((matches[0].similarity - 2.0 / 3.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::tokenize_words;
+
+/// Co-occurrence counts of the words seen around a word
+pub type Profile = HashMap<String, usize>;
+
+/// Lowercased word tokens of every corpus text
+fn tokenized(corpus: &[String]) -> Vec<Vec<String>> {
+    corpus
+        .iter()
+        .map(|text| {
+            tokenize_words(text)
+                .iter()
+                .map(|w| w.to_lowercase())
+                .collect()
+        })
+        .collect()
+}
+
+/// Add the words within `window` positions of `tokens[i]` to `profile`
+fn add_neighbours(profile: &mut Profile, tokens: &[String], i: usize, window: usize) {
+    let start = i.saturating_sub(window);
+    let end = (i + window + 1).min(tokens.len());
+    for (j, neighbour) in tokens.iter().enumerate().take(end).skip(start) {
+        if j != i {
+            *profile.entry(neighbour.clone()).or_insert(0) += 1;
+        }
+    }
+}
+
+/// Count the words occurring within `window` positions of `word` in each
+/// corpus text (case-insensitive; windows never cross texts)
+pub fn context_profile(word: &str, corpus: &[String], window: usize) -> Profile {
+    let word = word.to_lowercase();
+    let mut profile = Profile::new();
+    for tokens in tokenized(corpus) {
+        for i in (0..tokens.len()).filter(|&i| tokens[i] == word) {
+            add_neighbours(&mut profile, &tokens, i, window);
+        }
+    }
+    profile
+}
+
+/// Cosine similarity of two profiles; 0.0 when either is empty
+fn cosine(p1: &Profile, p2: &Profile) -> f64 {
+    let dot: f64 = p1
+        .iter()
+        .filter_map(|(w, &c)| p2.get(w).map(|&d| (c * d) as f64))
+        .sum();
+    let norm = |p: &Profile| p.values().map(|&c| (c * c) as f64).sum::<f64>().sqrt();
+    let norms = norm(p1) * norm(p2);
+    if norms == 0.0 {
+        0.0
+    } else {
+        dot / norms
+    }
+}
+
+/// How alike the contexts of two words are in the corpus, in [0, 1]
+pub fn distributional_similarity(w1: &str, w2: &str, corpus: &[String], window: usize) -> f64 {
+    cosine(
+        &context_profile(w1, corpus, window),
+        &context_profile(w2, corpus, window),
+    )
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NearSynonym {
+    pub word: String,
+    pub similarity: f64,
+}
+
+/// Context profiles of every word in a corpus, built once so synonym
+/// lookups don't rescan the texts
+#[wasm_bindgen]
+pub struct WasmContextIndex {
+    profiles: HashMap<String, Profile>,
+}
+
+#[wasm_bindgen]
+impl WasmContextIndex {
+    /// Index a list of texts, counting neighbours within `window` positions
+    #[wasm_bindgen(constructor)]
+    pub fn new(corpus: JsValue, window: usize) -> Result<WasmContextIndex, JsValue> {
+        let corpus_vec: Vec<String> = serde_wasm_bindgen::from_value(corpus)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse corpus: {}", e)))?;
+        Ok(Self::from_corpus(&corpus_vec, window))
+    }
+
+    /// The `n` words whose contexts are closest to `word`'s, as
+    /// `{ word, similarity }` sorted by similarity (descending)
+    pub fn find_near_synonyms(&self, word: &str, n: usize) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(&self.near_synonyms(word, n))
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+    }
+}
+
+impl WasmContextIndex {
+    pub fn from_corpus(corpus: &[String], window: usize) -> Self {
+        let mut profiles: HashMap<String, Profile> = HashMap::new();
+        for tokens in tokenized(corpus) {
+            for (i, token) in tokens.iter().enumerate() {
+                add_neighbours(
+                    profiles.entry(token.clone()).or_default(),
+                    &tokens,
+                    i,
+                    window,
+                );
+            }
+        }
+        WasmContextIndex { profiles }
+    }
+
+    /// Words sharing no context with `word` (or an unknown `word`) are left out
+    pub fn near_synonyms(&self, word: &str, n: usize) -> Vec<NearSynonym> {
+        let word = word.to_lowercase();
+        let Some(profile) = self.profiles.get(&word) else {
+            return Vec::new();
+        };
+
+        let mut synonyms: Vec<NearSynonym> = self
+            .profiles
+            .iter()
+            .filter(|(other, _)| **other != word)
+            .map(|(other, other_profile)| NearSynonym {
+                word: other.clone(),
+                similarity: cosine(profile, other_profile),
+            })
+            .filter(|s| s.similarity > 0.0)
+            .collect();
+        synonyms.sort_by(|a, b| {
+            b.similarity
+                .total_cmp(&a.similarity)
+                .then_with(|| a.word.cmp(&b.word))
+        });
+        synonyms.truncate(n);
+        synonyms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn corpus() -> Vec<String> {
+        [
+            "the cat drinks milk",
+            "the dog drinks water",
+            "a cat chases mice",
+            "a dog chases cars",
+            "she reads a book",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn test_context_profile() {
+        let profile = context_profile("Cat", &corpus(), 1);
+        assert_eq!(profile.get("the"), Some(&1));
+        assert_eq!(profile.get("drinks"), Some(&1));
+        assert_eq!(profile.get("chases"), Some(&1));
+        assert_eq!(profile.get("milk"), None);
+        assert!(context_profile("unicorn", &corpus(), 1).is_empty());
+    }
+
+    #[test]
+    fn test_distributional_similarity() {
+        let corpus = corpus();
+        let cat_dog = distributional_similarity("cat", "dog", &corpus, 1);
+        let cat_book = distributional_similarity("cat", "book", &corpus, 1);
+        assert!((cat_dog - 1.0).abs() < 1e-12);
+        assert!(cat_dog > cat_book);
+        assert_eq!(distributional_similarity("cat", "unicorn", &corpus, 1), 0.0);
+    }
+
+    #[test]
+    fn test_near_synonyms() {
+        let index = WasmContextIndex::from_corpus(&corpus(), 1);
+        let synonyms = index.near_synonyms("cat", 3);
+        assert_eq!(synonyms[0].word, "dog");
+        assert!(synonyms.len() <= 3);
+        assert!(synonyms.iter().all(|s| s.word != "cat"));
+        assert!(index.near_synonyms("unicorn", 3).is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// Fraction of a word's distinct characters that aren't known yet.
+/// A word without characters introduces nothing and scores 0.0.
+fn coverage_score(word: &str, known_chars: &[String]) -> f64 {
+    let unique: BTreeSet<char> = word.chars().collect();
+    if unique.is_empty() {
+        return 0.0;
+    }
+    let known: BTreeSet<char> = known_chars.iter().flat_map(|s| s.chars()).collect();
+    let new_chars = unique.iter().filter(|c| !known.contains(c)).count();
+    new_chars as f64 / unique.len() as f64
+}
+
+/// How many new characters a word teaches: the fraction of its distinct
+/// characters missing from the `known_chars` array
+#[wasm_bindgen]
+pub fn character_coverage_score(word: &str, known_chars: JsValue) -> Result<f64, JsValue> {
+    let known: Vec<String> = serde_wasm_bindgen::from_value(known_chars)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse known chars: {}", e)))?;
+
+    Ok(coverage_score(word, &known))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CoverageAnalysis {
+    pub covered_count: usize,
+    /// Distinct target characters
+    pub total_count: usize,
+    pub coverage_ratio: f64,
+    /// Target characters no vocabulary word contains, in target order
+    pub missing_chars: Vec<char>,
+}
+
+/// Which of the target characters appear in at least one vocabulary word.
+/// An empty target set is fully covered.
+fn coverage_analysis(vocabulary: &[String], target_chars: &[String]) -> CoverageAnalysis {
+    let present: BTreeSet<char> = vocabulary.iter().flat_map(|w| w.chars()).collect();
+
+    let mut targets: Vec<char> = Vec::new();
+    for c in target_chars.iter().flat_map(|s| s.chars()) {
+        if !targets.contains(&c) {
+            targets.push(c);
+        }
+    }
+    let missing_chars: Vec<char> = targets
+        .iter()
+        .filter(|c| !present.contains(c))
+        .copied()
+        .collect();
+
+    let total_count = targets.len();
+    let covered_count = total_count - missing_chars.len();
+    CoverageAnalysis {
+        covered_count,
+        total_count,
+        coverage_ratio: if total_count == 0 {
+            1.0
+        } else {
+            covered_count as f64 / total_count as f64
+        },
+        missing_chars,
+    }
+}
+
+/// Report how much of a target character set (e.g. all hiragana) a
+/// vocabulary covers as `{ covered_count, total_count, coverage_ratio, missing_chars }`
+#[wasm_bindgen]
+pub fn vocabulary_coverage_analysis(
+    vocabulary: JsValue,
+    target_chars: JsValue,
+) -> Result<JsValue, JsValue> {
+    let vocab_vec: Vec<String> = serde_wasm_bindgen::from_value(vocabulary)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse vocabulary: {}", e)))?;
+    let targets: Vec<String> = serde_wasm_bindgen::from_value(target_chars)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse target chars: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&coverage_analysis(&vocab_vec, &targets))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(list: &[&str]) -> Vec<String> {
+        list.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn test_coverage_score() {
+        assert_eq!(coverage_score("abc", &[]), 1.0);
+        assert_eq!(coverage_score("abc", &chars(&["a", "b", "c"])), 0.0);
+        assert_eq!(coverage_score("abcd", &chars(&["a", "b"])), 0.5);
+        // Repeated letters count once
+        assert_eq!(coverage_score("aab", &chars(&["a"])), 0.5);
+        assert_eq!(coverage_score("ねこ", &chars(&["ね"])), 0.5);
+        assert_eq!(coverage_score("", &[]), 0.0);
+    }
+
+    #[test]
+    fn test_coverage_analysis() {
+        let vocab = chars(&["hello", "world"]);
+        let alphabet: Vec<String> = ('a'..='z').map(|c| c.to_string()).collect();
+        let analysis = coverage_analysis(&vocab, &alphabet);
+
+        assert_eq!(analysis.total_count, 26);
+        assert_eq!(analysis.covered_count, 7);
+        let covered: BTreeSet<char> = ('a'..='z')
+            .filter(|c| !analysis.missing_chars.contains(c))
+            .collect();
+        assert_eq!(covered, "helowrd".chars().collect());
+        assert_eq!(analysis.missing_chars.len(), 19);
+        assert!((analysis.coverage_ratio - 7.0 / 26.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_coverage_analysis_edge_cases() {
+        let analysis = coverage_analysis(&[], &chars(&["あ", "い", "あ"]));
+        assert_eq!(analysis.total_count, 2);
+        assert_eq!(analysis.missing_chars, vec!['あ', 'い']);
+        assert_eq!(analysis.coverage_ratio, 0.0);
+
+        assert_eq!(coverage_analysis(&chars(&["cat"]), &[]).coverage_ratio, 1.0);
+    }
+}
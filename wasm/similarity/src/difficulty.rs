@@ -0,0 +1,399 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::phonetic::count_syllables;
+use crate::tokenize_words;
+
+/// Word length (in chars) at which the length component saturates
+const MAX_DIFFICULT_LENGTH: f64 = 15.0;
+/// Extra syllables (beyond the first) at which the syllable component saturates
+const MAX_EXTRA_SYLLABLES: f64 = 5.0;
+/// Frequency rank treated as "rarest" for the rarity component
+const MAX_FREQUENCY_RANK: f64 = 100_000.0;
+
+/// Relative weights of the components of `word_difficulty_score`
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct DifficultyWeights {
+    pub length: f64,
+    pub syllables: f64,
+    pub rarity: f64,
+}
+
+impl Default for DifficultyWeights {
+    fn default() -> Self {
+        DifficultyWeights {
+            length: 0.3,
+            syllables: 0.3,
+            rarity: 0.4,
+        }
+    }
+}
+
+/// Difficulty in [0, 1] as the weighted average of normalized length,
+/// syllable count and rarity. Rarity grows with the log of `frequency_rank`
+/// (1 = most common); a rank of 0 is treated as 1.
+pub(crate) fn difficulty_score(
+    word: &str,
+    frequency_rank: u32,
+    weights: &DifficultyWeights,
+) -> f64 {
+    let total_weight = weights.length + weights.syllables + weights.rarity;
+    if total_weight <= 0.0 {
+        return 0.0;
+    }
+
+    let length = (word.chars().count() as f64 / MAX_DIFFICULT_LENGTH).min(1.0);
+    let syllables = (count_syllables(word).saturating_sub(1) as f64 / MAX_EXTRA_SYLLABLES).min(1.0);
+    let rank = frequency_rank.max(1) as f64;
+    let rarity = (rank.ln() / MAX_FREQUENCY_RANK.ln()).min(1.0);
+
+    (weights.length * length + weights.syllables * syllables + weights.rarity * rarity)
+        / total_weight
+}
+
+/// Difficulty of a word in [0, 1] using the default weights
+#[wasm_bindgen]
+pub fn word_difficulty_score(word: &str, frequency_rank: u32) -> f64 {
+    difficulty_score(word, frequency_rank, &DifficultyWeights::default())
+}
+
+/// Difficulty of a word in [0, 1] using `{ length, syllables, rarity }` weights
+#[wasm_bindgen]
+pub fn word_difficulty_score_weighted(
+    word: &str,
+    frequency_rank: u32,
+    weights: JsValue,
+) -> Result<f64, JsValue> {
+    let weights: DifficultyWeights = serde_wasm_bindgen::from_value(weights)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse weights: {}", e)))?;
+
+    Ok(difficulty_score(word, frequency_rank, &weights))
+}
+
+/// Share of consonant letters that sit next to another consonant ("str", "ck").
+fn consonant_cluster_ratio(word: &str) -> f64 {
+    let letters: Vec<bool> = word
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .map(|c| !matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y'))
+        .collect();
+    let consonants = letters.iter().filter(|&&c| c).count();
+    if consonants == 0 {
+        return 0.0;
+    }
+
+    let clustered = (0..letters.len())
+        .filter(|&i| {
+            letters[i]
+                && ((i > 0 && letters[i - 1]) || letters.get(i + 1).copied().unwrap_or(false))
+        })
+        .count();
+    clustered as f64 / consonants as f64
+}
+
+/// Difficulty level from 1 (A1) to 5 (C2) combining length, syllable count,
+/// frequency rank and consonant clusters
+#[wasm_bindgen]
+pub fn cefr_difficulty(word: &str, frequency_rank: u32) -> u8 {
+    let weights = DifficultyWeights {
+        length: 0.3,
+        syllables: 0.25,
+        rarity: 0.3,
+    };
+    // difficulty_score averages over its weights; rescale so clusters get the remaining 0.15
+    let base = difficulty_score(word, frequency_rank, &weights) * 0.85;
+    let score = base + 0.15 * consonant_cluster_ratio(word);
+
+    match score {
+        s if s < 0.25 => 1,
+        s if s < 0.4 => 2,
+        s if s < 0.55 => 3,
+        s if s < 0.7 => 4,
+        _ => 5,
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadingDifficulty {
+    /// Mean word length in chars
+    pub avg_word_length: f64,
+    /// Fraction of words not found in the common word list
+    pub rare_word_ratio: f64,
+    /// Combined difficulty in [0, 1]
+    pub score: f64,
+}
+
+/// Estimate how hard a text is to read from its average word length and
+/// the share of words outside `common_words` (compared case-insensitively).
+/// Text without words scores 0.0 everywhere.
+fn reading_difficulty(text: &str, common_words: &[String]) -> ReadingDifficulty {
+    let words = tokenize_words(text);
+    if words.is_empty() {
+        return ReadingDifficulty {
+            avg_word_length: 0.0,
+            rare_word_ratio: 0.0,
+            score: 0.0,
+        };
+    }
+
+    let common: HashSet<String> = common_words.iter().map(|w| w.to_lowercase()).collect();
+    let total_length: usize = words.iter().map(|w| w.chars().count()).sum();
+    let rare = words
+        .iter()
+        .filter(|w| !common.contains(&w.to_lowercase()))
+        .count();
+
+    let avg_word_length = total_length as f64 / words.len() as f64;
+    let rare_word_ratio = rare as f64 / words.len() as f64;
+    let length = (avg_word_length / MAX_DIFFICULT_LENGTH).min(1.0);
+
+    ReadingDifficulty {
+        avg_word_length,
+        rare_word_ratio,
+        score: (length + rare_word_ratio) / 2.0,
+    }
+}
+
+/// Estimate the reading difficulty of a text as
+/// `{ avg_word_length, rare_word_ratio, score }`
+#[wasm_bindgen]
+pub fn estimate_reading_difficulty(text: &str, common_words: JsValue) -> Result<JsValue, JsValue> {
+    let common_vec: Vec<String> = serde_wasm_bindgen::from_value(common_words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse common words: {}", e)))?;
+
+    let difficulty = reading_difficulty(text, &common_vec);
+
+    serde_wasm_bindgen::to_value(&difficulty)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SpellingBeeLevels {
+    pub easy: Vec<String>,
+    pub medium: Vec<String>,
+    pub hard: Vec<String>,
+}
+
+/// Split words into difficulty terciles using `word_difficulty_score`, each
+/// tier ordered from easiest to hardest. Words missing from the frequency
+/// rank map are treated as the rarest.
+fn spelling_bee_levels(
+    words: &[String],
+    frequency_ranks: &HashMap<String, u32>,
+) -> SpellingBeeLevels {
+    let mut scored: Vec<(f64, &String)> = words
+        .iter()
+        .map(|w| {
+            let rank = frequency_ranks.get(w).copied().unwrap_or(u32::MAX);
+            (word_difficulty_score(w, rank), w)
+        })
+        .collect();
+    scored.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut levels = SpellingBeeLevels::default();
+    let total = scored.len();
+    for (i, (_, word)) in scored.into_iter().enumerate() {
+        let tier = match i * 3 / total {
+            0 => &mut levels.easy,
+            1 => &mut levels.medium,
+            _ => &mut levels.hard,
+        };
+        tier.push(word.clone());
+    }
+    levels
+}
+
+/// Partition words into `{ easy, medium, hard }` spelling bee tiers from a
+/// `{ word: frequency_rank }` map
+#[wasm_bindgen]
+pub fn create_spelling_bee_levels(words: JsValue, freq_map: JsValue) -> Result<JsValue, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+    let ranks: HashMap<String, u32> = serde_wasm_bindgen::from_value(freq_map)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse frequency map: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&spelling_bee_levels(&words_vec, &ranks))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FrequencyRank {
+    pub word: String,
+    /// 1 for the word guessed to be most frequent
+    pub rank: usize,
+}
+
+/// Guess frequency ranks without a corpus. Zipf's law of abbreviation says
+/// frequent words are short, so words are ordered by length, then syllables,
+/// then consonant clusters, with ties broken alphabetically.
+fn frequency_ranks(words: &[String]) -> Vec<FrequencyRank> {
+    let mut scored: Vec<(f64, &String)> = words
+        .iter()
+        .map(|w| {
+            let complexity =
+                w.chars().count() as f64 + count_syllables(w) as f64 + consonant_cluster_ratio(w);
+            (complexity, w)
+        })
+        .collect();
+    scored.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+
+    scored
+        .into_iter()
+        .enumerate()
+        .map(|(i, (_, word))| FrequencyRank {
+            word: word.clone(),
+            rank: i + 1,
+        })
+        .collect()
+}
+
+/// Estimate `{ word, rank }` frequency ranks from word shape alone, most
+/// frequent first, for vocabularies without frequency data
+#[wasm_bindgen]
+pub fn estimate_frequency_ranks(words: JsValue) -> Result<JsValue, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&frequency_ranks(&words_vec))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words;
+
+    #[test]
+    fn test_word_difficulty_score() {
+        let easy = word_difficulty_score("cat", 5);
+        let hard = word_difficulty_score("sesquipedalian", 90_000);
+        assert!(easy < hard);
+        assert!((0.0..=1.0).contains(&easy));
+        assert!((0.0..=1.0).contains(&hard));
+    }
+
+    #[test]
+    fn test_difficulty_weights() {
+        let rarity_only = DifficultyWeights {
+            length: 0.0,
+            syllables: 0.0,
+            rarity: 1.0,
+        };
+        assert_eq!(difficulty_score("sesquipedalian", 1, &rarity_only), 0.0);
+        assert!(difficulty_score("cat", 50_000, &rarity_only) > 0.9);
+
+        let none = DifficultyWeights {
+            length: 0.0,
+            syllables: 0.0,
+            rarity: 0.0,
+        };
+        assert_eq!(difficulty_score("cat", 10, &none), 0.0);
+    }
+
+    #[test]
+    fn test_consonant_cluster_ratio() {
+        assert_eq!(consonant_cluster_ratio("cat"), 0.0);
+        assert_eq!(consonant_cluster_ratio("strength"), 1.0);
+        assert_eq!(consonant_cluster_ratio("aeiou"), 0.0);
+    }
+
+    #[test]
+    fn test_cefr_difficulty() {
+        assert_eq!(cefr_difficulty("cat", 50), 1);
+        assert_eq!(cefr_difficulty("sesquipedalian", 90_000), 5);
+
+        for (word, rank) in [("", 0), ("a", 1), ("strengths", 100_000), ("x", u32::MAX)] {
+            let level = cefr_difficulty(word, rank);
+            assert!((1..=5).contains(&level), "{} -> {}", word, level);
+        }
+    }
+
+    #[test]
+    fn test_reading_difficulty() {
+        let common = words(&["the", "cat", "sat", "on", "mat"]);
+
+        let easy = reading_difficulty("The cat sat on the mat.", &common);
+        assert_eq!(easy.rare_word_ratio, 0.0);
+        let hard = reading_difficulty(
+            "Notwithstanding considerable epistemological reservations, the committee acquiesced.",
+            &common,
+        );
+        assert!(easy.score < hard.score);
+        assert!(easy.avg_word_length < hard.avg_word_length);
+        assert!(hard.rare_word_ratio > 0.8);
+        assert!((0.0..=1.0).contains(&hard.score));
+    }
+
+    #[test]
+    fn test_reading_difficulty_empty() {
+        let empty = reading_difficulty(" ... ", &[]);
+        assert_eq!(empty.score, 0.0);
+        assert_eq!(empty.avg_word_length, 0.0);
+    }
+
+    #[test]
+    fn test_spelling_bee_levels() {
+        let vocab = words(&[
+            "cat",
+            "sesquipedalian",
+            "dog",
+            "onomatopoeia",
+            "house",
+            "rhythm",
+            "sun",
+            "questionnaire",
+            "garden",
+        ]);
+        let ranks: HashMap<String, u32> = [("cat", 10), ("dog", 12), ("sun", 20), ("house", 50)]
+            .iter()
+            .map(|(w, r)| (w.to_string(), *r))
+            .collect();
+        let levels = spelling_bee_levels(&vocab, &ranks);
+
+        assert_eq!(levels.easy.len(), 3);
+        assert_eq!(levels.medium.len(), 3);
+        assert_eq!(levels.hard.len(), 3);
+        let mut all: Vec<&String> = levels
+            .easy
+            .iter()
+            .chain(&levels.medium)
+            .chain(&levels.hard)
+            .collect();
+        all.sort();
+        all.dedup();
+        assert_eq!(all.len(), vocab.len());
+
+        let average = |tier: &[String]| {
+            tier.iter()
+                .map(|w| word_difficulty_score(w, ranks.get(w).copied().unwrap_or(u32::MAX)))
+                .sum::<f64>()
+                / tier.len() as f64
+        };
+        assert!(average(&levels.easy) < average(&levels.hard));
+    }
+
+    #[test]
+    fn test_spelling_bee_levels_empty() {
+        let levels = spelling_bee_levels(&[], &HashMap::new());
+        assert!(levels.easy.is_empty() && levels.medium.is_empty() && levels.hard.is_empty());
+    }
+
+    #[test]
+    fn test_frequency_ranks() {
+        let list = words(&["sesquipedalian", "the", "strength", "water", "a"]);
+        let ranks = frequency_ranks(&list);
+        let rank_of = |word: &str| ranks.iter().find(|r| r.word == word).unwrap().rank;
+        assert!(rank_of("the") < rank_of("sesquipedalian"));
+        assert_eq!(rank_of("a"), 1);
+
+        let mut all: Vec<usize> = ranks.iter().map(|r| r.rank).collect();
+        all.sort();
+        assert_eq!(all, (1..=list.len()).collect::<Vec<_>>());
+        assert!(ranks.windows(2).all(|w| w[0].rank < w[1].rank));
+
+        assert!(frequency_ranks(&[]).is_empty());
+    }
+}
@@ -0,0 +1,658 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// Length of the codes produced by the original algorithm
+const CODE_LENGTH: usize = 4;
+
+/// Letter-by-letter state of a Double Metaphone encoding
+struct Encoder {
+    word: Vec<char>,
+    primary: String,
+    secondary: String,
+    slavo_germanic: bool,
+}
+
+impl Encoder {
+    fn new(s: &str) -> Self {
+        let upper = s.to_uppercase();
+        let slavo_germanic = upper.contains('W')
+            || upper.contains('K')
+            || upper.contains("CZ")
+            || upper.contains("WITZ");
+        Encoder {
+            word: upper.chars().collect(),
+            primary: String::new(),
+            secondary: String::new(),
+            slavo_germanic,
+        }
+    }
+
+    fn last(&self) -> isize {
+        self.word.len() as isize - 1
+    }
+
+    /// Char at `i`, or `'\0'` outside the word
+    fn at(&self, i: isize) -> char {
+        if i < 0 {
+            return '\0';
+        }
+        self.word.get(i as usize).copied().unwrap_or('\0')
+    }
+
+    /// Whether the `len` chars from `start` spell one of `options`
+    fn string_at(&self, start: isize, len: usize, options: &[&str]) -> bool {
+        if start < 0 || start as usize + len > self.word.len() {
+            return false;
+        }
+        let slice = &self.word[start as usize..start as usize + len];
+        options
+            .iter()
+            .any(|option| option.chars().eq(slice.iter().copied()))
+    }
+
+    fn is_vowel(&self, i: isize) -> bool {
+        matches!(self.at(i), 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+    }
+
+    fn add(&mut self, code: &str) {
+        self.add_alt(code, code);
+    }
+
+    fn add_alt(&mut self, primary: &str, secondary: &str) {
+        self.primary.push_str(primary);
+        self.secondary.push_str(secondary);
+    }
+
+    /// Skip over a doubled `c` at `i + 1`
+    fn step_past(&self, i: isize, c: char) -> isize {
+        if self.at(i + 1) == c {
+            i + 2
+        } else {
+            i + 1
+        }
+    }
+
+    fn starts_germanic(&self) -> bool {
+        self.string_at(0, 4, &["VAN ", "VON "]) || self.string_at(0, 3, &["SCH"])
+    }
+
+    fn encode_c(&mut self, i: isize) -> isize {
+        // Germanic "ach" as in "bacher", but not "aching"
+        if i > 1
+            && !self.is_vowel(i - 2)
+            && self.string_at(i - 1, 3, &["ACH"])
+            && self.at(i + 2) != 'I'
+            && (self.at(i + 2) != 'E' || self.string_at(i - 2, 6, &["BACHER", "MACHER"]))
+        {
+            self.add("K");
+            return i + 2;
+        }
+        if i == 0 && self.string_at(i, 6, &["CAESAR"]) {
+            self.add("S");
+            return i + 2;
+        }
+        if self.string_at(i, 4, &["CHIA"]) {
+            self.add("K");
+            return i + 2;
+        }
+        if self.string_at(i, 2, &["CH"]) {
+            if i > 0 && self.string_at(i, 4, &["CHAE"]) {
+                self.add_alt("K", "X");
+                return i + 2;
+            }
+            // Greek roots: "character", "chorus"
+            if i == 0
+                && (self.string_at(i + 1, 5, &["HARAC", "HARIS"])
+                    || self.string_at(i + 1, 3, &["HOR", "HYM", "HIA", "HEM"]))
+                && !self.string_at(0, 5, &["CHORE"])
+            {
+                self.add("K");
+                return i + 2;
+            }
+            if self.starts_germanic()
+                || self.string_at(i - 2, 6, &["ORCHES", "ARCHIT", "ORCHID"])
+                || self.string_at(i + 2, 1, &["T", "S"])
+                || ((i == 0 || self.string_at(i - 1, 1, &["A", "O", "U", "E"]))
+                    && self.string_at(
+                        i + 2,
+                        1,
+                        &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "],
+                    ))
+            {
+                self.add("K");
+            } else if i == 0 {
+                self.add("X");
+            } else if self.string_at(0, 2, &["MC"]) {
+                self.add("K");
+            } else {
+                self.add_alt("X", "K");
+            }
+            return i + 2;
+        }
+        if self.string_at(i, 2, &["CZ"]) && !self.string_at(i - 2, 4, &["WICZ"]) {
+            self.add_alt("S", "X");
+            return i + 2;
+        }
+        if self.string_at(i + 1, 3, &["CIA"]) {
+            self.add("X");
+            return i + 3;
+        }
+        // Double 'c' but not "McClellan"
+        if self.string_at(i, 2, &["CC"]) && !(i == 1 && self.at(0) == 'M') {
+            if self.string_at(i + 2, 1, &["I", "E", "H"]) && !self.string_at(i + 2, 2, &["HU"]) {
+                if (i == 1 && self.at(0) == 'A') || self.string_at(i - 1, 5, &["UCCEE", "UCCES"]) {
+                    self.add("KS");
+                } else {
+                    self.add("X");
+                }
+                return i + 3;
+            }
+            self.add("K");
+            return i + 2;
+        }
+        if self.string_at(i, 2, &["CK", "CG", "CQ"]) {
+            self.add("K");
+            return i + 2;
+        }
+        if self.string_at(i, 2, &["CI", "CE", "CY"]) {
+            if self.string_at(i, 3, &["CIO", "CIE", "CIA"]) {
+                self.add_alt("S", "X");
+            } else {
+                self.add("S");
+            }
+            return i + 2;
+        }
+
+        self.add("K");
+        if self.string_at(i + 1, 2, &[" C", " Q", " G"]) {
+            i + 3
+        } else if self.string_at(i + 1, 1, &["C", "K", "Q"])
+            && !self.string_at(i + 1, 2, &["CE", "CI"])
+        {
+            i + 2
+        } else {
+            i + 1
+        }
+    }
+
+    fn encode_g(&mut self, i: isize) -> isize {
+        if self.at(i + 1) == 'H' {
+            if i > 0 && !self.is_vowel(i - 1) {
+                self.add("K");
+                return i + 2;
+            }
+            if i == 0 {
+                // "ghislane", "ghost"
+                self.add(if self.at(i + 2) == 'I' { "J" } else { "K" });
+                return i + 2;
+            }
+            // Silent as in "hugh", "bough", "broughton"
+            if self.string_at(i - 2, 1, &["B", "H", "D"])
+                || self.string_at(i - 3, 1, &["B", "H", "D"])
+                || self.string_at(i - 4, 1, &["B", "H"])
+            {
+                return i + 2;
+            }
+            // "laugh", "tough"
+            if i > 2
+                && self.at(i - 1) == 'U'
+                && self.string_at(i - 3, 1, &["C", "G", "L", "R", "T"])
+            {
+                self.add("F");
+            } else if self.at(i - 1) != 'I' {
+                self.add("K");
+            }
+            return i + 2;
+        }
+        if self.at(i + 1) == 'N' {
+            if i == 1 && self.is_vowel(0) && !self.slavo_germanic {
+                self.add_alt("KN", "N");
+            } else if !self.string_at(i + 2, 2, &["EY"]) && !self.slavo_germanic {
+                self.add_alt("N", "KN");
+            } else {
+                self.add("KN");
+            }
+            return i + 2;
+        }
+        if self.string_at(i + 1, 2, &["LI"]) && !self.slavo_germanic {
+            self.add_alt("KL", "L");
+            return i + 2;
+        }
+        if i == 0
+            && (self.at(i + 1) == 'Y'
+                || self.string_at(
+                    i + 1,
+                    2,
+                    &[
+                        "ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER",
+                    ],
+                ))
+        {
+            self.add_alt("K", "J");
+            return i + 2;
+        }
+        if (self.string_at(i + 1, 2, &["ER"]) || self.at(i + 1) == 'Y')
+            && !self.string_at(0, 6, &["DANGER", "RANGER", "MANGER"])
+            && !self.string_at(i - 1, 1, &["E", "I"])
+            && !self.string_at(i - 1, 3, &["RGY", "OGY"])
+        {
+            self.add_alt("K", "J");
+            return i + 2;
+        }
+        if self.string_at(i + 1, 1, &["E", "I", "Y"]) || self.string_at(i - 1, 4, &["AGGI", "OGGI"])
+        {
+            if self.starts_germanic() || self.string_at(i + 1, 2, &["ET"]) {
+                self.add("K");
+            } else if self.string_at(i + 1, 4, &["IER "]) {
+                self.add("J");
+            } else {
+                self.add_alt("J", "K");
+            }
+            return i + 2;
+        }
+
+        self.add("K");
+        self.step_past(i, 'G')
+    }
+
+    fn encode_j(&mut self, i: isize) -> isize {
+        // Spanish "Jose", "San Jacinto"
+        if self.string_at(i, 4, &["JOSE"]) || self.string_at(0, 4, &["SAN "]) {
+            if (i == 0 && self.at(i + 4) == ' ') || self.string_at(0, 4, &["SAN "]) {
+                self.add("H");
+            } else {
+                self.add_alt("J", "H");
+            }
+            return i + 1;
+        }
+        if i == 0 {
+            self.add_alt("J", "A");
+        } else if self.is_vowel(i - 1)
+            && !self.slavo_germanic
+            && matches!(self.at(i + 1), 'A' | 'O')
+        {
+            self.add_alt("J", "H");
+        } else if i == self.last() {
+            self.add_alt("J", "");
+        } else if !self.string_at(i + 1, 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+            && !self.string_at(i - 1, 1, &["S", "K", "L"])
+        {
+            self.add("J");
+        }
+        self.step_past(i, 'J')
+    }
+
+    fn encode_s(&mut self, i: isize) -> isize {
+        // Silent in "island", "carlisle"
+        if self.string_at(i - 1, 3, &["ISL", "YSL"]) {
+            return i + 1;
+        }
+        if i == 0 && self.string_at(i, 5, &["SUGAR"]) {
+            self.add_alt("X", "S");
+            return i + 1;
+        }
+        if self.string_at(i, 2, &["SH"]) {
+            // Germanic "-sheim", "-sholm"
+            if self.string_at(i + 1, 4, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                self.add("S");
+            } else {
+                self.add("X");
+            }
+            return i + 2;
+        }
+        if self.string_at(i, 3, &["SIO", "SIA"]) || self.string_at(i, 4, &["SIAN"]) {
+            if self.slavo_germanic {
+                self.add("S");
+            } else {
+                self.add_alt("S", "X");
+            }
+            return i + 3;
+        }
+        // German "Schmidt" may be anglicised as "Smith", and vice versa
+        if (i == 0 && self.string_at(i + 1, 1, &["M", "N", "L", "W"]))
+            || self.string_at(i + 1, 1, &["Z"])
+        {
+            self.add_alt("S", "X");
+            return self.step_past(i, 'Z');
+        }
+        if self.string_at(i, 2, &["SC"]) {
+            if self.at(i + 2) == 'H' {
+                // Dutch "schooner", "schenker"
+                if self.string_at(i + 3, 2, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                    if self.string_at(i + 3, 2, &["ER", "EN"]) {
+                        self.add_alt("X", "SK");
+                    } else {
+                        self.add("SK");
+                    }
+                } else if i == 0 && !self.is_vowel(3) && self.at(3) != 'W' {
+                    self.add_alt("X", "S");
+                } else {
+                    self.add("X");
+                }
+                return i + 3;
+            }
+            if self.string_at(i + 2, 1, &["I", "E", "Y"]) {
+                self.add("S");
+            } else {
+                self.add("SK");
+            }
+            return i + 3;
+        }
+
+        // French "resnais", "artois"
+        if i == self.last() && self.string_at(i - 2, 2, &["AI", "OI"]) {
+            self.add_alt("", "S");
+        } else {
+            self.add("S");
+        }
+        if self.string_at(i + 1, 1, &["S", "Z"]) {
+            i + 2
+        } else {
+            i + 1
+        }
+    }
+
+    fn encode_w(&mut self, i: isize) -> isize {
+        if self.string_at(i, 2, &["WR"]) {
+            self.add("R");
+            return i + 2;
+        }
+        if i == 0 && (self.is_vowel(i + 1) || self.string_at(i, 2, &["WH"])) {
+            // "Wasserman" may be said with a 'v'
+            if self.is_vowel(i + 1) {
+                self.add_alt("A", "F");
+            } else {
+                self.add("A");
+            }
+        }
+        // Polish "Filipowicz", or an Anglo-Saxon "Arnow"
+        if (i == self.last() && self.is_vowel(i - 1))
+            || self.string_at(i - 1, 5, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.string_at(0, 3, &["SCH"])
+        {
+            self.add_alt("", "F");
+            return i + 1;
+        }
+        if self.string_at(i, 4, &["WICZ", "WITZ"]) {
+            self.add_alt("TS", "FX");
+            return i + 4;
+        }
+        i + 1
+    }
+
+    /// Encode the char at `i`, returning the index of the next one to look at
+    fn encode_at(&mut self, i: isize) -> isize {
+        match self.at(i) {
+            'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                // Vowels only count at the start
+                if i == 0 {
+                    self.add("A");
+                }
+                i + 1
+            }
+            'B' => {
+                self.add("P");
+                self.step_past(i, 'B')
+            }
+            'Ç' => {
+                self.add("S");
+                i + 1
+            }
+            'C' => self.encode_c(i),
+            'D' => {
+                if self.string_at(i, 2, &["DG"]) {
+                    if self.string_at(i + 2, 1, &["I", "E", "Y"]) {
+                        // "edge"
+                        self.add("J");
+                        i + 3
+                    } else {
+                        self.add("TK");
+                        i + 2
+                    }
+                } else {
+                    self.add("T");
+                    if self.string_at(i, 2, &["DT", "DD"]) {
+                        i + 2
+                    } else {
+                        i + 1
+                    }
+                }
+            }
+            'F' => {
+                self.add("F");
+                self.step_past(i, 'F')
+            }
+            'G' => self.encode_g(i),
+            'H' => {
+                // Only kept between vowels or at the start before one
+                if (i == 0 || self.is_vowel(i - 1)) && self.is_vowel(i + 1) {
+                    self.add("H");
+                    i + 2
+                } else {
+                    i + 1
+                }
+            }
+            'J' => self.encode_j(i),
+            'K' => {
+                self.add("K");
+                self.step_past(i, 'K')
+            }
+            'L' => {
+                if self.at(i + 1) == 'L' {
+                    // Spanish "cabrillo", "gallegos"
+                    let last = self.last();
+                    if (i == last - 2 && self.string_at(i - 1, 4, &["ILLO", "ILLA", "ALLE"]))
+                        || ((self.string_at(last - 1, 2, &["AS", "OS"])
+                            || self.string_at(last, 1, &["A", "O"]))
+                            && self.string_at(i - 1, 4, &["ALLE"]))
+                    {
+                        self.add_alt("L", "");
+                    } else {
+                        self.add("L");
+                    }
+                    i + 2
+                } else {
+                    self.add("L");
+                    i + 1
+                }
+            }
+            'M' => {
+                self.add("M");
+                // Silent 'b' in "dumb", "thumb", "dumber"
+                let silent_b = self.string_at(i - 1, 3, &["UMB"])
+                    && (i + 1 == self.last() || self.string_at(i + 2, 2, &["ER"]));
+                if silent_b || self.at(i + 1) == 'M' {
+                    i + 2
+                } else {
+                    i + 1
+                }
+            }
+            'N' => {
+                self.add("N");
+                self.step_past(i, 'N')
+            }
+            'Ñ' => {
+                self.add("N");
+                i + 1
+            }
+            'P' => {
+                if self.at(i + 1) == 'H' {
+                    self.add("F");
+                    return i + 2;
+                }
+                self.add("P");
+                if self.string_at(i + 1, 1, &["P", "B"]) {
+                    i + 2
+                } else {
+                    i + 1
+                }
+            }
+            'Q' => {
+                self.add("K");
+                self.step_past(i, 'Q')
+            }
+            'R' => {
+                // French "rogier", but not "hochmeier"
+                if i == self.last()
+                    && !self.slavo_germanic
+                    && self.string_at(i - 2, 2, &["IE"])
+                    && !self.string_at(i - 4, 2, &["ME", "MA"])
+                {
+                    self.add_alt("", "R");
+                } else {
+                    self.add("R");
+                }
+                self.step_past(i, 'R')
+            }
+            'S' => self.encode_s(i),
+            'T' => {
+                if self.string_at(i, 4, &["TION"]) || self.string_at(i, 3, &["TIA", "TCH"]) {
+                    self.add("X");
+                    return i + 3;
+                }
+                if self.string_at(i, 2, &["TH"]) || self.string_at(i, 3, &["TTH"]) {
+                    // "Thomas", "Thames"
+                    if self.string_at(i + 2, 2, &["OM", "AM"]) || self.starts_germanic() {
+                        self.add("T");
+                    } else {
+                        self.add_alt("0", "T");
+                    }
+                    return i + 2;
+                }
+                self.add("T");
+                if self.string_at(i + 1, 1, &["T", "D"]) {
+                    i + 2
+                } else {
+                    i + 1
+                }
+            }
+            'V' => {
+                self.add("F");
+                self.step_past(i, 'V')
+            }
+            'W' => self.encode_w(i),
+            'X' => {
+                // French "breaux"
+                let last = self.last();
+                if !(i == last
+                    && (self.string_at(i - 3, 3, &["IAU", "EAU"])
+                        || self.string_at(i - 2, 2, &["AU", "OU"])))
+                {
+                    self.add("KS");
+                }
+                if self.string_at(i + 1, 1, &["C", "X"]) {
+                    i + 2
+                } else {
+                    i + 1
+                }
+            }
+            'Z' => {
+                if self.at(i + 1) == 'H' {
+                    // Chinese pinyin "Zhao"
+                    self.add("J");
+                    return i + 2;
+                }
+                if self.string_at(i + 1, 2, &["ZO", "ZI", "ZA"])
+                    || (self.slavo_germanic && i > 0 && self.at(i - 1) != 'T')
+                {
+                    self.add_alt("S", "TS");
+                } else {
+                    self.add("S");
+                }
+                self.step_past(i, 'Z')
+            }
+            _ => i + 1,
+        }
+    }
+}
+
+/// Lawrence Philips' Double Metaphone: a primary code for the most likely
+/// pronunciation and a secondary one for an alternate (often foreign)
+/// pronunciation, both at most four chars. '0' stands for "th" and 'X' for
+/// "sh"; the codes are equal when only one reading is plausible.
+pub(crate) fn double_metaphone(s: &str) -> (String, String) {
+    let mut encoder = Encoder::new(s);
+    let len = encoder.word.len() as isize;
+
+    let mut i = 0;
+    // Initial letters not pronounced: "gnome", "knight", "pneumatic", "wrong", "psychology"
+    if encoder.string_at(0, 2, &["GN", "KN", "PN", "WR", "PS"]) {
+        i += 1;
+    }
+    // Initial 'x' is pronounced 'z' as in "Xavier"
+    if encoder.at(0) == 'X' {
+        encoder.add("S");
+        i += 1;
+    }
+
+    while i < len && (encoder.primary.len() < CODE_LENGTH || encoder.secondary.len() < CODE_LENGTH)
+    {
+        i = encoder.encode_at(i);
+    }
+
+    encoder.primary.truncate(CODE_LENGTH);
+    encoder.secondary.truncate(CODE_LENGTH);
+    (encoder.primary, encoder.secondary)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DoubleMetaphoneCodes {
+    pub primary: String,
+    pub secondary: String,
+}
+
+/// Double Metaphone codes of a word as `{ primary, secondary }`
+#[wasm_bindgen]
+pub fn double_metaphone_encode(s: &str) -> JsValue {
+    let (primary, secondary) = double_metaphone(s);
+    serde_wasm_bindgen::to_value(&DoubleMetaphoneCodes { primary, secondary })
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(s: &str) -> (String, String) {
+        double_metaphone(s)
+    }
+
+    fn pair(primary: &str, secondary: &str) -> (String, String) {
+        (primary.to_string(), secondary.to_string())
+    }
+
+    #[test]
+    fn test_germanic_names() {
+        assert_eq!(codes("Schmidt"), pair("XMT", "SMT"));
+        assert_eq!(codes("Schneider"), pair("XNTR", "SNTR"));
+        assert_eq!(codes("Smith"), pair("SM0", "XMT"));
+    }
+
+    #[test]
+    fn test_simple_words_have_one_code() {
+        for word in ["cat", "dog", "table", "bread"] {
+            let (primary, secondary) = codes(word);
+            assert_eq!(primary, secondary, "{}", word);
+        }
+        assert_eq!(codes("cat"), pair("KT", "KT"));
+        assert_eq!(codes("table"), pair("TPL", "TPL"));
+    }
+
+    #[test]
+    fn test_silent_and_special_letters() {
+        assert_eq!(codes("knight").0, "NT");
+        assert_eq!(codes("laugh").0, "LF");
+        assert_eq!(codes("Xavier").0, "SF");
+        assert_eq!(codes("Thomas").0, "TMS");
+        assert_eq!(codes("Wasserman"), pair("ASRM", "FSRM"));
+        assert_eq!(codes("Jose"), pair("JS", "HS"));
+        assert_eq!(codes("San Jose").0, "SNHS");
+    }
+
+    #[test]
+    fn test_code_length_and_empty() {
+        let (primary, secondary) = codes("supercalifragilistic");
+        assert!(primary.len() <= CODE_LENGTH && secondary.len() <= CODE_LENGTH);
+        assert_eq!(codes(""), pair("", ""));
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::similarity_score;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MatchMetrics {
+    pub precision: f64,
+    pub recall: f64,
+    pub f1: f64,
+}
+
+/// Harmonic mean of precision and recall, 0.0 when both are 0
+fn f1_score(precision: f64, recall: f64) -> f64 {
+    if precision + recall == 0.0 {
+        0.0
+    } else {
+        2.0 * precision * recall / (precision + recall)
+    }
+}
+
+/// Precision, recall and F1 of predicted words against the expected ones,
+/// compared exactly and ignoring duplicates. A metric whose denominator is
+/// empty is 0.0.
+fn recall_precision(predicted: &[String], ground_truth: &[String]) -> MatchMetrics {
+    let predicted: HashSet<&String> = predicted.iter().collect();
+    let truth: HashSet<&String> = ground_truth.iter().collect();
+    let hits = predicted.intersection(&truth).count() as f64;
+
+    let ratio = |total: usize| if total == 0 { 0.0 } else { hits / total as f64 };
+    let precision = ratio(predicted.len());
+    let recall = ratio(truth.len());
+
+    MatchMetrics {
+        precision,
+        recall,
+        f1: f1_score(precision, recall),
+    }
+}
+
+/// Score predicted matches against a ground truth list as
+/// `{ precision, recall, f1 }`
+#[wasm_bindgen]
+pub fn compute_recall_precision(
+    predicted: JsValue,
+    ground_truth: JsValue,
+) -> Result<JsValue, JsValue> {
+    let predicted_vec: Vec<String> = serde_wasm_bindgen::from_value(predicted)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse predicted words: {}", e)))?;
+    let truth_vec: Vec<String> = serde_wasm_bindgen::from_value(ground_truth)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse ground truth: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&recall_precision(&predicted_vec, &truth_vec))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+/// Number of 0.01 steps scanned by `calibrate_threshold`
+const CALIBRATION_STEPS: usize = 100;
+
+/// Threshold in [0, 1] (0.01 resolution) maximizing F1 when pairs scoring at
+/// least the threshold are predicted to match. Every threshold between the
+/// hardest negative and the hardest positive usually scores the same, so
+/// the middle of the first best-scoring run is returned to leave a margin
+/// on both sides.
+fn best_threshold(positives: &[[String; 2]], negatives: &[[String; 2]]) -> Result<f64, String> {
+    if positives.is_empty() {
+        return Err("At least one positive pair is required".to_string());
+    }
+
+    let score = |pairs: &[[String; 2]]| -> Vec<f64> {
+        pairs.iter().map(|[a, b]| similarity_score(a, b)).collect()
+    };
+    let positive_scores = score(positives);
+    let negative_scores = score(negatives);
+
+    let f1_scores: Vec<f64> = (0..=CALIBRATION_STEPS)
+        .map(|step| {
+            let threshold = step as f64 / CALIBRATION_STEPS as f64;
+            let hits = positive_scores.iter().filter(|&&s| s >= threshold).count();
+            let false_alarms = negative_scores.iter().filter(|&&s| s >= threshold).count();
+            let predicted = hits + false_alarms;
+            let precision = if predicted == 0 {
+                0.0
+            } else {
+                hits as f64 / predicted as f64
+            };
+            let recall = hits as f64 / positive_scores.len() as f64;
+            f1_score(precision, recall)
+        })
+        .collect();
+
+    let best = f1_scores.iter().copied().fold(0.0, f64::max);
+    let start = f1_scores.iter().position(|&f| f == best).unwrap_or(0);
+    let run = f1_scores[start..]
+        .iter()
+        .take_while(|&&f| f == best)
+        .count();
+    Ok((start + (run - 1) / 2) as f64 / CALIBRATION_STEPS as f64)
+}
+
+/// Find the similarity threshold best separating `[word, word]` pairs that
+/// should match from pairs that shouldn't
+#[wasm_bindgen]
+pub fn calibrate_threshold(
+    positive_pairs: JsValue,
+    negative_pairs: JsValue,
+) -> Result<f64, JsValue> {
+    let positives: Vec<[String; 2]> = serde_wasm_bindgen::from_value(positive_pairs)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse positive pairs: {}", e)))?;
+    let negatives: Vec<[String; 2]> = serde_wasm_bindgen::from_value(negative_pairs)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse negative pairs: {}", e)))?;
+
+    best_threshold(&positives, &negatives).map_err(|e| JsValue::from_str(&e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words;
+
+    #[test]
+    fn test_recall_precision_subsets() {
+        let half_found = recall_precision(&words(&["cat"]), &words(&["cat", "bat"]));
+        assert_eq!(half_found.precision, 1.0);
+        assert_eq!(half_found.recall, 0.5);
+
+        let extra = recall_precision(&words(&["cat", "bat"]), &words(&["cat"]));
+        assert_eq!(extra.precision, 0.5);
+        assert_eq!(extra.recall, 1.0);
+        assert!((extra.f1 - 2.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_recall_precision_equal_and_empty() {
+        let list = words(&["cat", "bat", "rat"]);
+        let same = recall_precision(&list, &list);
+        assert_eq!((same.precision, same.recall, same.f1), (1.0, 1.0, 1.0));
+
+        let none = recall_precision(&[], &list);
+        assert_eq!((none.precision, none.recall, none.f1), (0.0, 0.0, 0.0));
+    }
+
+    fn pairs(list: &[(&str, &str)]) -> Vec<[String; 2]> {
+        list.iter()
+            .map(|(a, b)| [a.to_string(), b.to_string()])
+            .collect()
+    }
+
+    #[test]
+    fn test_best_threshold_separates_pairs() {
+        // Positives score 0.8 or more, negatives at most 0.625
+        let positives = pairs(&[("colour", "color"), ("houses", "house"), ("helo", "hello")]);
+        let negatives = pairs(&[
+            ("kitten", "sitting"),
+            ("sunday", "saturday"),
+            ("cat", "dog"),
+        ]);
+        let threshold = best_threshold(&positives, &negatives).unwrap();
+        assert!((0.7..=0.9).contains(&threshold), "got {}", threshold);
+
+        let positive_hits = positives
+            .iter()
+            .all(|[a, b]| similarity_score(a, b) >= threshold);
+        assert!(positive_hits);
+    }
+
+    #[test]
+    fn test_best_threshold_requires_positives() {
+        assert!(best_threshold(&[], &pairs(&[("cat", "dog")])).is_err());
+        assert!(best_threshold(&pairs(&[("cat", "cat")]), &[]).is_ok());
+    }
+}
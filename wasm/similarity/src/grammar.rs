@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::similarity_score;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SlotError {
+    pub slot: String,
+    pub expected: String,
+    pub actual: String,
+    pub similarity: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GrammarResult {
+    /// False when either sentence does not fit the template
+    pub parsed: bool,
+    pub is_correct: bool,
+    /// Slots whose words differ, in template order
+    pub slot_errors: Vec<SlotError>,
+    /// Similarity of the whole sentences
+    pub similarity: f64,
+}
+
+/// Split a sentence into words, dropping sentence-final punctuation.
+fn sentence_tokens(sentence: &str) -> Vec<&str> {
+    sentence
+        .split_whitespace()
+        .map(|t| t.trim_end_matches(['.', '!', '?', ',']))
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Fill the `{slot}` placeholders of a whitespace-separated template from a
+/// sentence. Literal template words must match (case-insensitive) and every
+/// slot takes exactly one word; returns `None` if the sentence doesn't fit.
+fn parse_template<'a>(template: &'a str, sentence: &'a str) -> Option<Vec<(&'a str, &'a str)>> {
+    let pattern: Vec<&str> = template.split_whitespace().collect();
+    let tokens = sentence_tokens(sentence);
+    if pattern.len() != tokens.len() {
+        return None;
+    }
+
+    let mut slots = Vec::new();
+    for (part, token) in pattern.iter().zip(tokens) {
+        match part.strip_prefix('{').and_then(|p| p.strip_suffix('}')) {
+            Some(name) => slots.push((name, token)),
+            None if part.eq_ignore_ascii_case(token) => {}
+            None => return None,
+        }
+    }
+    Some(slots)
+}
+
+/// Compare a learner's sentence with the expected one slot by slot using
+/// a template such as `{pronoun} {verb} to {place}`.
+pub(crate) fn grammar_token_distance(
+    expected: &str,
+    actual: &str,
+    template: &str,
+) -> GrammarResult {
+    let similarity = similarity_score(&expected.to_lowercase(), &actual.to_lowercase());
+
+    let (Some(expected_slots), Some(actual_slots)) = (
+        parse_template(template, expected),
+        parse_template(template, actual),
+    ) else {
+        return GrammarResult {
+            parsed: false,
+            is_correct: expected.trim().eq_ignore_ascii_case(actual.trim()),
+            slot_errors: Vec::new(),
+            similarity,
+        };
+    };
+
+    let slot_errors: Vec<SlotError> = expected_slots
+        .iter()
+        .zip(&actual_slots)
+        .filter(|((_, e), (_, a))| !e.eq_ignore_ascii_case(a))
+        .map(|((slot, e), (_, a))| SlotError {
+            slot: slot.to_string(),
+            expected: e.to_string(),
+            actual: a.to_string(),
+            similarity: similarity_score(&e.to_lowercase(), &a.to_lowercase()),
+        })
+        .collect();
+
+    GrammarResult {
+        parsed: true,
+        is_correct: slot_errors.is_empty(),
+        slot_errors,
+        similarity,
+    }
+}
+
+/// Check a sentence answer against the expected one using a slot template,
+/// returning `{ parsed, is_correct, slot_errors, similarity }`
+#[wasm_bindgen]
+pub fn check_grammar_answer(
+    expected: &str,
+    actual: &str,
+    template: &str,
+) -> Result<JsValue, JsValue> {
+    let result = grammar_token_distance(expected, actual, template);
+
+    serde_wasm_bindgen::to_value(&result)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = "{pronoun} {verb} to {place}";
+
+    #[test]
+    fn test_grammar_slot_error() {
+        let result = grammar_token_distance("She goes to school.", "She go to school", TEMPLATE);
+        assert!(result.parsed);
+        assert!(!result.is_correct);
+        assert_eq!(result.slot_errors.len(), 1);
+        assert_eq!(result.slot_errors[0].slot, "verb");
+        assert_eq!(result.slot_errors[0].expected, "goes");
+        assert_eq!(result.slot_errors[0].actual, "go");
+    }
+
+    #[test]
+    fn test_grammar_multiple_slots() {
+        let result = grammar_token_distance("He walks to work", "They walk to work", TEMPLATE);
+        let slots: Vec<&str> = result.slot_errors.iter().map(|e| e.slot.as_str()).collect();
+        assert_eq!(slots, vec!["pronoun", "verb"]);
+    }
+
+    #[test]
+    fn test_grammar_correct_answer() {
+        let result = grammar_token_distance("She goes to school", "she goes to school!", TEMPLATE);
+        assert!(result.parsed);
+        assert!(result.is_correct);
+        assert!(result.slot_errors.is_empty());
+    }
+
+    #[test]
+    fn test_grammar_parse_failure() {
+        let result = grammar_token_distance("She goes to school", "She goes school", TEMPLATE);
+        assert!(!result.parsed);
+        assert!(!result.is_correct);
+        assert!(result.slot_errors.is_empty());
+        assert!(result.similarity > 0.5);
+
+        let result = grammar_token_distance("She goes to school", "She goes at school", TEMPLATE);
+        assert!(!result.parsed);
+    }
+}
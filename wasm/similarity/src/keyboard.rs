@@ -0,0 +1,219 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{levenshtein_distance, MatchResult};
+
+/// Cost of hitting a key next to the intended one
+const ADJACENT_KEY_COST: f64 = 0.5;
+/// Cost of swapping two letters typed with different hands ("teh" for "the"),
+/// the classic rhythm slip of touch typists
+const CROSS_HAND_TRANSPOSITION_COST: f64 = 0.5;
+/// Horizontal shift of each row relative to the number row
+const ROW_STAGGER: [f64; 4] = [0.0, 0.5, 0.75, 1.25];
+/// Columns `0..HAND_SPLIT` of every row are typed with the left hand
+const HAND_SPLIT: usize = 5;
+
+/// Keyboard layouts whose key adjacency can weight typo corrections
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyboardLayout {
+    Qwerty,
+    Azerty,
+    Dvorak,
+}
+
+impl KeyboardLayout {
+    /// Parse a layout name as passed from JS (`"qwerty"`, `"azerty"` or `"dvorak"`)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "qwerty" => Some(KeyboardLayout::Qwerty),
+            "azerty" => Some(KeyboardLayout::Azerty),
+            "dvorak" => Some(KeyboardLayout::Dvorak),
+            _ => None,
+        }
+    }
+
+    fn rows(&self) -> [&'static str; 4] {
+        match self {
+            KeyboardLayout::Qwerty => ["1234567890", "qwertyuiop", "asdfghjkl;", "zxcvbnm,./"],
+            KeyboardLayout::Azerty => ["1234567890", "azertyuiop", "qsdfghjklm", "wxcvbn,;:!"],
+            KeyboardLayout::Dvorak => ["1234567890", "',.pyfgcrl", "aoeuidhtns", ";qjkxbmwvz"],
+        }
+    }
+
+    /// Row and column of the key producing `c`, ignoring case
+    fn key(&self, c: char) -> Option<(usize, usize)> {
+        let c = c.to_lowercase().next()?;
+        self.rows()
+            .iter()
+            .enumerate()
+            .find_map(|(row, keys)| keys.chars().position(|k| k == c).map(|col| (row, col)))
+    }
+
+    /// Whether `a` and `b` sit on neighbouring keys (same or adjacent row)
+    fn adjacent(&self, a: char, b: char) -> bool {
+        let (Some((r1, c1)), Some((r2, c2))) = (self.key(a), self.key(b)) else {
+            return false;
+        };
+        let x1 = c1 as f64 + ROW_STAGGER[r1];
+        let x2 = c2 as f64 + ROW_STAGGER[r2];
+        r1.abs_diff(r2) <= 1 && (x1 - x2).abs() <= 1.0 && (r1, c1) != (r2, c2)
+    }
+
+    fn different_hands(&self, a: char, b: char) -> bool {
+        match (self.key(a), self.key(b)) {
+            (Some((_, c1)), Some((_, c2))) => (c1 < HAND_SPLIT) != (c2 < HAND_SPLIT),
+            _ => false,
+        }
+    }
+}
+
+fn substitution_cost(a: char, b: char, layout: Option<KeyboardLayout>) -> f64 {
+    match layout {
+        _ if a == b => 0.0,
+        Some(layout) if layout.adjacent(a, b) => ADJACENT_KEY_COST,
+        _ => 1.0,
+    }
+}
+
+fn transposition_cost(a: char, b: char, layout: Option<KeyboardLayout>) -> f64 {
+    match layout {
+        Some(layout) if layout.different_hands(a, b) => CROSS_HAND_TRANSPOSITION_COST,
+        _ => 1.0,
+    }
+}
+
+/// Edit distance where substitutions of neighbouring keys and transpositions
+/// across hands are cheaper on the given layout. `None` weighs every edit
+/// (including adjacent transpositions) as 1.
+pub(crate) fn keyboard_distance(s1: &str, s2: &str, layout: Option<KeyboardLayout>) -> f64 {
+    let a: Vec<char> = s1.chars().collect();
+    let b: Vec<char> = s2.chars().collect();
+
+    let mut d = vec![vec![0.0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i as f64;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j as f64;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let mut value = (d[i - 1][j] + 1.0) // deletion
+                .min(d[i][j - 1] + 1.0) // insertion
+                .min(d[i - 1][j - 1] + substitution_cost(a[i - 1], b[j - 1], layout));
+            if i > 1
+                && j > 1
+                && a[i - 1] == b[j - 2]
+                && a[i - 2] == b[j - 1]
+                && a[i - 1] != a[i - 2]
+            {
+                value = value.min(d[i - 2][j - 2] + transposition_cost(a[i - 2], a[i - 1], layout));
+            }
+            d[i][j] = value;
+        }
+    }
+
+    d[a.len()][b.len()]
+}
+
+/// Similarity in [0, 1] based on `keyboard_distance`
+pub(crate) fn keyboard_similarity(s1: &str, s2: &str, layout: Option<KeyboardLayout>) -> f64 {
+    let max_len = s1.chars().count().max(s2.chars().count());
+    if max_len == 0 {
+        return 1.0;
+    }
+    (1.0 - keyboard_distance(s1, s2, layout) / max_len as f64).max(0.0)
+}
+
+/// Score every word against the query with keyboard-aware weights, sorted
+/// by similarity (descending). `distance` stays the plain edit count.
+fn keyboard_matches(query: &str, words: &[String], layout: KeyboardLayout) -> Vec<MatchResult> {
+    let mut matches: Vec<MatchResult> = words
+        .iter()
+        .map(|word| MatchResult {
+            word: word.clone(),
+            distance: levenshtein_distance(query, word),
+            similarity: keyboard_similarity(query, word, Some(layout)),
+            is_exact: query == word,
+        })
+        .collect();
+
+    matches.sort();
+    matches
+}
+
+/// Find fuzzy matches forgiving slips to neighbouring keys on a
+/// `"qwerty"`, `"azerty"` or `"dvorak"` keyboard
+#[wasm_bindgen]
+pub fn find_keyboard_matches(
+    query: &str,
+    words: JsValue,
+    layout: &str,
+) -> Result<JsValue, JsValue> {
+    let layout = KeyboardLayout::parse(layout)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown keyboard layout: {}", layout)))?;
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    let matches = keyboard_matches(query, &words_vec, layout);
+
+    serde_wasm_bindgen::to_value(&matches)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words;
+
+    #[test]
+    fn test_transposition_cheaper_on_qwerty() {
+        let qwerty = keyboard_similarity("teh", "the", Some(KeyboardLayout::Qwerty));
+        let uniform = keyboard_similarity("teh", "the", None);
+        assert!(qwerty > uniform);
+        assert_eq!(keyboard_distance("teh", "the", None), 1.0);
+    }
+
+    #[test]
+    fn test_adjacent_key_substitution() {
+        let layout = Some(KeyboardLayout::Qwerty);
+        // 's' is next to 'a' on QWERTY, 'p' is not
+        assert_eq!(keyboard_distance("cst", "cat", layout), 0.5);
+        assert_eq!(keyboard_distance("cpt", "cat", layout), 1.0);
+        // On AZERTY 'm' sits next to 'l'
+        assert_eq!(
+            keyboard_distance("mt", "lt", Some(KeyboardLayout::Azerty)),
+            0.5
+        );
+        assert_eq!(keyboard_distance("mt", "lt", layout), 1.0);
+    }
+
+    #[test]
+    fn test_keyboard_distance_identity() {
+        for layout in [KeyboardLayout::Qwerty, KeyboardLayout::Dvorak] {
+            assert_eq!(keyboard_distance("house", "house", Some(layout)), 0.0);
+            assert_eq!(keyboard_similarity("", "", Some(layout)), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_keyboard_matches() {
+        let vocab = words(&["the", "tea", "tel"]);
+        let matches = keyboard_matches("teh", &vocab, KeyboardLayout::Qwerty);
+        assert_eq!(matches[0].word, "the");
+        assert_eq!(matches[0].distance, 2);
+    }
+
+    #[test]
+    fn test_parse_layout() {
+        assert_eq!(
+            KeyboardLayout::parse("QWERTY"),
+            Some(KeyboardLayout::Qwerty)
+        );
+        assert_eq!(
+            KeyboardLayout::parse("dvorak"),
+            Some(KeyboardLayout::Dvorak)
+        );
+        assert_eq!(KeyboardLayout::parse("colemak"), None);
+    }
+}
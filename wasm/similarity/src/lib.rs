@@ -1,6 +1,60 @@
-use wasm_bindgen::prelude::*;
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+use wasm_bindgen::prelude::*;
+
+mod alignment;
+mod autocomplete;
+#[cfg(any(test, feature = "bench"))]
+mod bench;
+mod cache;
+mod casefold;
+mod context;
+mod coverage;
+mod difficulty;
+mod double_metaphone;
+mod evaluation;
+mod grammar;
+mod keyboard;
+pub mod native;
+mod phonetic;
+mod puzzles;
+mod quiz;
+mod scheduling;
+#[cfg(target_arch = "x86_64")]
+mod simd;
+mod spelling;
+mod transliterate;
+mod trie;
+mod unicode_block;
+mod vocabulary;
 
+pub use alignment::*;
+pub use autocomplete::*;
+#[cfg(feature = "bench")]
+pub use bench::*;
+pub use cache::*;
+pub use casefold::*;
+pub use context::*;
+pub use coverage::*;
+pub use difficulty::*;
+pub use double_metaphone::*;
+pub use evaluation::*;
+pub use grammar::*;
+pub use keyboard::*;
+pub use phonetic::*;
+pub use puzzles::*;
+pub use quiz::*;
+pub use scheduling::*;
+pub use spelling::*;
+pub use transliterate::*;
+pub use trie::*;
+pub use unicode_block::*;
+pub use vocabulary::*;
+
+// Trims the `.wasm` size at the cost of fragmentation in long-running
+// sessions; see the `wee_alloc` feature in Cargo.toml.
 #[cfg(feature = "wee_alloc")]
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
@@ -9,6 +63,16 @@ static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 /// This measures the minimum number of single-character edits
 /// (insertions, deletions, or substitutions) required to change one word into the other.
 fn levenshtein_distance(s1: &str, s2: &str) -> usize {
+    #[cfg(target_arch = "x86_64")]
+    if let Some(distance) = simd::try_levenshtein(s1, s2) {
+        return distance;
+    }
+
+    levenshtein_scalar(s1, s2)
+}
+
+/// Portable Levenshtein DP, used on targets and inputs without a SIMD path.
+fn levenshtein_scalar(s1: &str, s2: &str) -> usize {
     let len1 = s1.chars().count();
     let len2 = s2.chars().count();
 
@@ -19,20 +83,29 @@ fn levenshtein_distance(s1: &str, s2: &str) -> usize {
         return len1;
     }
 
+    levenshtein_matrix(s1, s2)[len1][len2]
+}
+
+/// Full `(len1 + 1) x (len2 + 1)` Levenshtein DP table over chars, where
+/// `matrix[i][j]` is the distance between the first `i` chars of `s1` and
+/// the first `j` chars of `s2`.
+pub(crate) fn levenshtein_matrix(s1: &str, s2: &str) -> Vec<Vec<usize>> {
+    let s1_chars: Vec<char> = s1.chars().collect();
+    let s2_chars: Vec<char> = s2.chars().collect();
+    let len1 = s1_chars.len();
+    let len2 = s2_chars.len();
+
     let mut matrix = vec![vec![0; len2 + 1]; len1 + 1];
 
     // Initialize first row and column
-    for i in 0..=len1 {
-        matrix[i][0] = i;
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[0] = i;
     }
-    for j in 0..=len2 {
-        matrix[0][j] = j;
+    for (j, cell) in matrix[0].iter_mut().enumerate() {
+        *cell = j;
     }
 
     // Fill in the matrix
-    let s1_chars: Vec<char> = s1.chars().collect();
-    let s2_chars: Vec<char> = s2.chars().collect();
-
     for i in 1..=len1 {
         for j in 1..=len2 {
             let cost = if s1_chars[i - 1] == s2_chars[j - 1] {
@@ -47,27 +120,234 @@ fn levenshtein_distance(s1: &str, s2: &str) -> usize {
         }
     }
 
-    matrix[len1][len2]
+    matrix
 }
 
 /// Calculate similarity score (0.0 to 1.0) based on Levenshtein distance.
 /// 1.0 means identical, 0.0 means completely different.
+/// Two empty strings are identical and score 1.0; the result is never NaN.
 fn similarity_score(s1: &str, s2: &str) -> f64 {
-    let distance = levenshtein_distance(s1, s2);
+    similarity_score_exact(s1, s2).2
+}
+
+/// The inputs of `similarity_score` alongside its result, as `(distance,
+/// max_len, similarity)`, so callers near a threshold can redo the division
+/// themselves. `max_len` is the longer string's length in bytes, which is
+/// what the score is normalized by; it is 0 for two empty strings.
+fn similarity_score_exact(s1: &str, s2: &str) -> (usize, usize, f64) {
     let max_len = s1.len().max(s2.len());
+    if max_len == 0 {
+        return (0, 0, 1.0);
+    }
+    let distance = levenshtein_distance(s1, s2);
+    (
+        distance,
+        max_len,
+        similarity_from_distance(distance, s1, s2),
+    )
+}
 
+/// `similarity_score` of two strings already known to be `distance` edits
+/// apart, without rerunning the DP
+pub(crate) fn similarity_from_distance(distance: usize, s1: &str, s2: &str) -> f64 {
+    let max_len = s1.len().max(s2.len());
     if max_len == 0 {
         return 1.0;
     }
+    1.0 - distance as f64 / max_len as f64
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SimilarityDebug {
+    pub distance: usize,
+    /// Length in bytes of the longer string
+    pub max_len: usize,
+    /// `1.0 - distance / max_len`, or 1.0 when `max_len` is 0
+    pub similarity: f64,
+}
 
-    1.0 - (distance as f64 / max_len as f64)
+/// Calculate similarity together with the distance and length it was
+/// derived from, as `{ distance, max_len, similarity }`
+#[wasm_bindgen]
+pub fn calculate_similarity_debug(s1: &str, s2: &str) -> JsValue {
+    let (distance, max_len, similarity) = similarity_score_exact(s1, s2);
+    serde_wasm_bindgen::to_value(&SimilarityDebug {
+        distance,
+        max_len,
+        similarity,
+    })
+    .unwrap_or(JsValue::NULL)
 }
 
-#[derive(Serialize, Deserialize)]
+/// Levenshtein distance divided by the length of the longer string in chars:
+/// 0.0 for identical strings (including two empty ones), 1.0 when nothing
+/// lines up
+#[wasm_bindgen]
+pub fn normalized_edit_distance(s1: &str, s2: &str) -> f64 {
+    let max_len = s1.chars().count().max(s2.chars().count());
+    if max_len == 0 {
+        return 0.0;
+    }
+    levenshtein_distance(s1, s2) as f64 / max_len as f64
+}
+
+/// Levenshtein distance (in chars) divided by the longer string's length in
+/// bytes, as `calculate_similarity` normalizes. Equal to
+/// `normalized_edit_distance` for ASCII input.
+#[wasm_bindgen]
+pub fn normalized_edit_distance_bytes(s1: &str, s2: &str) -> f64 {
+    let max_len = s1.len().max(s2.len());
+    if max_len == 0 {
+        return 0.0;
+    }
+    levenshtein_distance(s1, s2) as f64 / max_len as f64
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MatchResult {
     pub word: String,
     pub distance: usize,
     pub similarity: f64,
+    pub is_exact: bool,
+}
+
+/// Metric used to score a query/candidate pair
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Levenshtein distance and similarity on the raw strings
+    Levenshtein,
+    /// `abbreviation_similarity`, with the Levenshtein distance kept for reference
+    Abbreviation,
+    /// Levenshtein distance and similarity between the phonetic codes
+    Phonetic(PhoneticAlgorithm),
+}
+
+impl MatchResult {
+    /// Score `candidate` against `query` with Levenshtein distance
+    pub fn from_pair(query: &str, candidate: &str) -> Self {
+        MatchResult::from_pair_with_algorithm(query, candidate, Algorithm::Levenshtein)
+    }
+
+    /// Score `candidate` against `query` with the given algorithm.
+    /// `is_exact` is only set when both strings are identical.
+    pub fn from_pair_with_algorithm(query: &str, candidate: &str, algo: Algorithm) -> Self {
+        let (distance, similarity) = match algo {
+            Algorithm::Levenshtein => (
+                levenshtein_distance(query, candidate),
+                similarity_score(query, candidate),
+            ),
+            Algorithm::Abbreviation => (
+                levenshtein_distance(query, candidate),
+                abbreviation_similarity(query, candidate),
+            ),
+            Algorithm::Phonetic(phonetic) => {
+                let (code1, code2) = (phonetic.encode(query), phonetic.encode(candidate));
+                (
+                    levenshtein_distance(&code1, &code2),
+                    similarity_score(&code1, &code2),
+                )
+            }
+        };
+
+        MatchResult {
+            word: candidate.to_string(),
+            distance,
+            similarity,
+            is_exact: query == candidate,
+        }
+    }
+}
+
+/// Results order by similarity (descending), then word and distance (ascending),
+/// matching the order returned by `find_matches`.
+impl Ord for MatchResult {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other
+            .similarity
+            .total_cmp(&self.similarity)
+            .then_with(|| self.word.cmp(&other.word))
+            .then_with(|| self.distance.cmp(&other.distance))
+    }
+}
+
+impl PartialOrd for MatchResult {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for MatchResult {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for MatchResult {}
+
+/// Options controlling how `find_matches_opts` filters candidates
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompareOptions {
+    /// Minimum similarity (0.0 to 1.0) a word needs to be returned
+    pub threshold: f64,
+    /// Skip words identical to the query (distance 0)
+    pub exclude_exact: bool,
+}
+
+impl Default for CompareOptions {
+    fn default() -> Self {
+        CompareOptions {
+            threshold: 0.0,
+            exclude_exact: false,
+        }
+    }
+}
+
+/// Find the most similar word, or `None` for an empty list.
+fn best_match(query: &str, words: &[String]) -> Option<MatchResult> {
+    words
+        .iter()
+        .map(|word| MatchResult::from_pair(query, word))
+        .max_by(|a, b| {
+            a.similarity
+                .partial_cmp(&b.similarity)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+}
+
+/// Score every word against the query and keep the ones passing the options,
+/// sorted by similarity (descending) with ties in alphabetical order.
+fn match_words(query: &str, words: &[String], options: &CompareOptions) -> Vec<MatchResult> {
+    let mut matches: Vec<MatchResult> = words
+        .iter()
+        .map(|word| MatchResult::from_pair(query, word))
+        .filter(|m| m.similarity >= options.threshold)
+        .filter(|m| !(options.exclude_exact && m.distance == 0))
+        .collect();
+
+    // Sort by similarity (descending)
+    matches.sort();
+    matches
+}
+
+/// The `n` most similar words with similarity in `[threshold, max_similarity]`,
+/// excluding exact matches, sorted like `match_words`.
+fn top_n_matches(
+    query: &str,
+    words: &[String],
+    n: usize,
+    threshold: f64,
+    max_similarity: f64,
+) -> Vec<MatchResult> {
+    let options = CompareOptions {
+        threshold,
+        exclude_exact: true,
+    };
+    let mut matches = match_words(query, words, &options);
+    matches.retain(|m| m.similarity <= max_similarity);
+    matches.dedup_by(|a, b| a.word == b.word);
+    matches.truncate(n);
+    matches
 }
 
 /// Calculate Levenshtein distance between two strings (WASM export)
@@ -94,22 +374,240 @@ pub fn find_matches(query: &str, words: JsValue, threshold: f64) -> Result<JsVal
     let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
         .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
 
-    let mut matches: Vec<MatchResult> = words_vec
-        .iter()
-        .map(|word| {
-            let distance = levenshtein_distance(query, word);
-            let similarity = similarity_score(query, word);
-            MatchResult {
-                word: word.clone(),
-                distance,
-                similarity,
-            }
+    let options = CompareOptions {
+        threshold,
+        ..CompareOptions::default()
+    };
+    let matches = match_words(query, &words_vec, &options);
+
+    serde_wasm_bindgen::to_value(&matches)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+/// Find fuzzy matches in a list of words using a `CompareOptions` object
+#[wasm_bindgen]
+pub fn find_matches_opts(
+    query: &str,
+    words: JsValue,
+    options: JsValue,
+) -> Result<JsValue, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+    let options: CompareOptions = serde_wasm_bindgen::from_value(options)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse options: {}", e)))?;
+
+    let matches = match_words(query, &words_vec, &options);
+
+    serde_wasm_bindgen::to_value(&matches)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+/// `MatchResult` with a single-precision similarity, half the float payload
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MatchResultF32 {
+    pub word: String,
+    pub distance: usize,
+    pub similarity: f32,
+}
+
+/// Same as `match_words` with the threshold applied to the `f32` similarity,
+/// so every returned score compares `>= threshold` on the JS side as well.
+fn match_words_f32(query: &str, words: &[String], threshold: f32) -> Vec<MatchResultF32> {
+    match_words(query, words, &CompareOptions::default())
+        .into_iter()
+        .map(|m| MatchResultF32 {
+            word: m.word,
+            distance: m.distance,
+            similarity: m.similarity as f32,
         })
         .filter(|m| m.similarity >= threshold)
-        .collect();
+        .collect()
+}
 
-    // Sort by similarity (descending)
-    matches.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap());
+/// Calculate similarity score between two strings as `f32` (WASM export)
+#[wasm_bindgen]
+pub fn calculate_similarity_f32(s1: &str, s2: &str) -> f32 {
+    similarity_score(s1, s2) as f32
+}
+
+/// Find fuzzy matches in a list of words with `f32` similarities
+#[wasm_bindgen]
+pub fn find_matches_f32(query: &str, words: JsValue, threshold: f32) -> Result<JsValue, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    let matches = match_words_f32(query, &words_vec, threshold);
+
+    serde_wasm_bindgen::to_value(&matches)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+/// Similarity of the query to every word, in input order.
+fn similarities_for(query: &str, words: &[String]) -> Vec<f64> {
+    words.iter().map(|w| similarity_score(query, w)).collect()
+}
+
+/// Edit distance of the query to every word, in input order.
+fn distances_for(query: &str, words: &[String]) -> Vec<u32> {
+    words
+        .iter()
+        .map(|w| levenshtein_distance(query, w) as u32)
+        .collect()
+}
+
+/// Similarity of the query to each word as a `Float64Array` (`result[i]`
+/// belongs to `words[i]`). The scores are copied into the typed array in one
+/// block instead of being serialized one object at a time.
+#[wasm_bindgen]
+pub fn find_similarities_fast(
+    query: &str,
+    words: JsValue,
+) -> Result<js_sys::Float64Array, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    Ok(js_sys::Float64Array::from(
+        &similarities_for(query, &words_vec)[..],
+    ))
+}
+
+/// Edit distance of the query to each word as a `Uint32Array`
+#[wasm_bindgen]
+pub fn find_distances_fast(query: &str, words: JsValue) -> Result<js_sys::Uint32Array, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    Ok(js_sys::Uint32Array::from(
+        &distances_for(query, &words_vec)[..],
+    ))
+}
+
+/// Split a NUL-delimited UTF-8 word list. Empty segments (from a trailing
+/// or doubled NUL) are skipped, so empty words can't be encoded.
+fn parse_null_delimited(bytes: &[u8]) -> Result<Vec<String>, String> {
+    bytes
+        .split(|&b| b == 0)
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            std::str::from_utf8(segment)
+                .map(str::to_string)
+                .map_err(|e| format!("Failed to parse words: {}", e))
+        })
+        .collect()
+}
+
+/// Find fuzzy matches in a NUL-delimited UTF-8 word list (as produced by
+/// `TextEncoder` on `words.join("\0")`), avoiding JSON parsing of the input
+#[wasm_bindgen]
+pub fn find_matches_binary(
+    query: &str,
+    words_bytes: &[u8],
+    threshold: f64,
+) -> Result<JsValue, JsValue> {
+    let words_vec = parse_null_delimited(words_bytes).map_err(|e| JsValue::from_str(&e))?;
+
+    let options = CompareOptions {
+        threshold,
+        ..CompareOptions::default()
+    };
+    let matches = match_words(query, &words_vec, &options);
+
+    serde_wasm_bindgen::to_value(&matches)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+/// Encode matches as `count: u32`, then per match `word_len: u32`, the UTF-8
+/// word, `distance: u32` and `similarity: f64`, all little-endian.
+/// `is_exact` is left out since it equals `distance == 0`.
+fn pack_results(results: &[MatchResult]) -> Vec<u8> {
+    let words_len: usize = results.iter().map(|r| r.word.len()).sum();
+    let mut bytes = Vec::with_capacity(4 + results.len() * 16 + words_len);
+    bytes.extend_from_slice(&(results.len() as u32).to_le_bytes());
+    for result in results {
+        bytes.extend_from_slice(&(result.word.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(result.word.as_bytes());
+        bytes.extend_from_slice(&(result.distance as u32).to_le_bytes());
+        bytes.extend_from_slice(&result.similarity.to_le_bytes());
+    }
+    bytes
+}
+
+/// Find fuzzy matches returned as one packed buffer instead of an array of
+/// objects; decode it with `unpackResults` from `lib/packed-matches.ts`
+#[wasm_bindgen]
+pub fn find_matches_packed(
+    query: &str,
+    words: JsValue,
+    threshold: f64,
+) -> Result<Box<[u8]>, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    let options = CompareOptions {
+        threshold,
+        ..CompareOptions::default()
+    };
+    let matches = match_words(query, &words_vec, &options);
+
+    Ok(pack_results(&matches).into_boxed_slice())
+}
+
+/// How many words are scored between two deadline checks
+const DEADLINE_CHECK_INTERVAL: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimedMatches {
+    pub results: Vec<MatchResult>,
+    /// False when the deadline cut the search short
+    pub completed: bool,
+    pub words_processed: usize,
+}
+
+/// `match_words` that stops early once `now()` (in milliseconds) passes
+/// `deadline`, checking every `DEADLINE_CHECK_INTERVAL` words. Partial
+/// results are sorted like complete ones.
+fn match_words_until(
+    query: &str,
+    words: &[String],
+    threshold: f64,
+    deadline: f64,
+    now: impl Fn() -> f64,
+) -> TimedMatches {
+    let mut results = Vec::new();
+    let mut words_processed = 0;
+    for (i, word) in words.iter().enumerate() {
+        if i % DEADLINE_CHECK_INTERVAL == 0 && i > 0 && now() > deadline {
+            break;
+        }
+        let m = MatchResult::from_pair(query, word);
+        if m.similarity >= threshold {
+            results.push(m);
+        }
+        words_processed += 1;
+    }
+
+    results.sort();
+    TimedMatches {
+        results,
+        completed: words_processed == words.len(),
+        words_processed,
+    }
+}
+
+/// Find fuzzy matches, giving up after `timeout_ms` and returning
+/// `{ results, completed, words_processed }` with whatever was found so far
+#[wasm_bindgen]
+pub fn find_matches_timeout_ms(
+    query: &str,
+    words: JsValue,
+    threshold: f64,
+    timeout_ms: f64,
+) -> Result<JsValue, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    let deadline = js_sys::Date::now() + timeout_ms;
+    let matches = match_words_until(query, &words_vec, threshold, deadline, js_sys::Date::now);
 
     serde_wasm_bindgen::to_value(&matches)
         .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
@@ -121,52 +619,1604 @@ pub fn find_best_match(query: &str, words: JsValue) -> Result<JsValue, JsValue>
     let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
         .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
 
-    if words_vec.is_empty() {
-        return Ok(JsValue::NULL);
+    match best_match(query, &words_vec) {
+        Some(result) => serde_wasm_bindgen::to_value(&result)
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e))),
+        None => Ok(JsValue::NULL),
     }
+}
 
-    let best = words_vec
-        .iter()
-        .map(|word| {
-            let distance = levenshtein_distance(query, word);
-            let similarity = similarity_score(query, word);
-            MatchResult {
-                word: word.clone(),
-                distance,
-                similarity,
-            }
-        })
-        .max_by(|a, b| a.similarity.partial_cmp(&b.similarity).unwrap());
+/// The best match if it reaches `threshold`, otherwise `None` (even when
+/// another word would have passed, since only the best one is considered)
+fn best_match_above(query: &str, words: &[String], threshold: f64) -> Option<MatchResult> {
+    best_match(query, words).filter(|m| m.similarity >= threshold)
+}
+
+/// Find the best match in a list of words, or `null` if it scores below `threshold`
+#[wasm_bindgen]
+pub fn find_best_match_threshold(
+    query: &str,
+    words: JsValue,
+    threshold: f64,
+) -> Result<JsValue, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
 
-    match best {
+    match best_match_above(query, &words_vec, threshold) {
         Some(result) => serde_wasm_bindgen::to_value(&result)
             .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e))),
         None => Ok(JsValue::NULL),
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+#[derive(Serialize, Deserialize)]
+pub struct Correction {
+    pub corrected: String,
+    pub confidence: f64,
+}
 
-    #[test]
-    fn test_levenshtein_distance() {
-        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
-        assert_eq!(levenshtein_distance("", "test"), 4);
-        assert_eq!(levenshtein_distance("test", ""), 4);
-        assert_eq!(levenshtein_distance("same", "same"), 0);
+/// Minimum similarity a vocabulary word needs to be offered as a correction
+const CORRECTION_MIN_SIMILARITY: f64 = 0.5;
+
+/// Pick the most likely intended word for a typo, if any is close enough.
+fn correct_word(typo: &str, vocabulary: &[String]) -> Option<Correction> {
+    best_match(typo, vocabulary)
+        .filter(|m| m.similarity > CORRECTION_MIN_SIMILARITY)
+        .map(|m| Correction {
+            corrected: m.word,
+            confidence: m.similarity,
+        })
+}
+
+/// Return the best-guess correction for a typo as `{ corrected, confidence }`,
+/// or `null` when no vocabulary word is similar enough
+#[wasm_bindgen]
+pub fn correct_typo(typo: &str, vocabulary: JsValue) -> Result<JsValue, JsValue> {
+    let vocab_vec: Vec<String> = serde_wasm_bindgen::from_value(vocabulary)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse vocabulary: {}", e)))?;
+
+    match correct_word(typo, &vocab_vec) {
+        Some(correction) => serde_wasm_bindgen::to_value(&correction)
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e))),
+        None => Ok(JsValue::NULL),
     }
+}
 
-    #[test]
-    fn test_similarity_score() {
-        assert_eq!(similarity_score("same", "same"), 1.0);
-        assert!(similarity_score("test", "tost") > 0.7);
-        assert!(similarity_score("hello", "world") < 0.5);
+#[derive(Serialize, Deserialize)]
+pub struct TypoCorrection {
+    pub original: String,
+    pub corrected: String,
+    pub confidence: f64,
+}
+
+/// Correct every typo against the same vocabulary, preserving input order.
+/// Words without a close enough candidate are kept as-is with confidence 0.0.
+fn correct_words(typos: &[String], vocabulary: &[String]) -> Vec<TypoCorrection> {
+    typos
+        .iter()
+        .map(|typo| match correct_word(typo, vocabulary) {
+            Some(correction) => TypoCorrection {
+                original: typo.clone(),
+                corrected: correction.corrected,
+                confidence: correction.confidence,
+            },
+            None => TypoCorrection {
+                original: typo.clone(),
+                corrected: typo.clone(),
+                confidence: 0.0,
+            },
+        })
+        .collect()
+}
+
+/// Correct a list of typos at once, returning `{ original, corrected, confidence }`
+/// for each input in the same order
+#[wasm_bindgen]
+pub fn batch_correct_typos(typos: JsValue, vocabulary: JsValue) -> Result<JsValue, JsValue> {
+    let typos_vec: Vec<String> = serde_wasm_bindgen::from_value(typos)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse typos: {}", e)))?;
+    let vocab_vec: Vec<String> = serde_wasm_bindgen::from_value(vocabulary)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse vocabulary: {}", e)))?;
+
+    let corrections = correct_words(&typos_vec, &vocab_vec);
+
+    serde_wasm_bindgen::to_value(&corrections)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+/// Split text into words on Unicode word boundaries (UAX #29).
+/// Punctuation and whitespace never form tokens on their own.
+fn tokenize_words(s: &str) -> Vec<String> {
+    s.unicode_words().map(|w| w.to_string()).collect()
+}
+
+/// Split text into word tokens (WASM export)
+#[wasm_bindgen]
+pub fn tokenize(s: &str) -> Result<JsValue, JsValue> {
+    serde_wasm_bindgen::to_value(&tokenize_words(s))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize tokens: {}", e)))
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MergeRecord {
+    pub removed: String,
+    pub kept_as: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeduplicationResult {
+    pub words: Vec<String>,
+    pub merges: Vec<MergeRecord>,
+}
+
+/// Find the cluster representative of `i`, compressing the path on the way.
+fn find_root(parents: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    let mut node = i;
+    while parents[node] != root {
+        let next = parents[node];
+        parents[node] = root;
+        node = next;
     }
+    root
+}
 
-    #[test]
-    fn test_is_similar() {
-        assert!(is_similar("hello", "hallo", 0.8));
-        assert!(!is_similar("hello", "world", 0.8));
+/// Cluster words whose similarity reaches `merge_threshold` (transitively) and
+/// keep the lexicographically smallest word of each cluster, in input order.
+fn deduplicate_words(words: &[String], merge_threshold: f64) -> DeduplicationResult {
+    let mut parents: Vec<usize> = (0..words.len()).collect();
+
+    for i in 0..words.len() {
+        for j in (i + 1)..words.len() {
+            if similarity_score(&words[i], &words[j]) >= merge_threshold {
+                let a = find_root(&mut parents, i);
+                let b = find_root(&mut parents, j);
+                if a != b {
+                    parents[b] = a;
+                }
+            }
+        }
+    }
+
+    // Pick the smallest word of each cluster as its representative
+    let mut representative: Vec<Option<usize>> = vec![None; words.len()];
+    for i in 0..words.len() {
+        let root = find_root(&mut parents, i);
+        match representative[root] {
+            Some(kept) if words[kept] <= words[i] => {}
+            _ => representative[root] = Some(i),
+        }
+    }
+
+    let mut result = DeduplicationResult {
+        words: Vec::new(),
+        merges: Vec::new(),
+    };
+    for (i, word) in words.iter().enumerate() {
+        let root = find_root(&mut parents, i);
+        let kept = representative[root].unwrap_or(i);
+        if kept == i {
+            result.words.push(word.clone());
+        } else {
+            result.merges.push(MergeRecord {
+                removed: word.clone(),
+                kept_as: words[kept].clone(),
+            });
+        }
+    }
+    result
+}
+
+/// Merge near-identical vocabulary entries, returning `{ words, merges }`
+/// where `merges` lists every `{ removed, kept_as }` pair
+#[wasm_bindgen]
+pub fn deduplicate_vocabulary(words: JsValue, merge_threshold: f64) -> Result<JsValue, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    let result = deduplicate_words(&words_vec, merge_threshold);
+
+    serde_wasm_bindgen::to_value(&result)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NearDuplicate {
+    pub is_duplicate: bool,
+    /// Closest vocabulary word, `None` for an empty vocabulary
+    pub most_similar: Option<String>,
+    pub similarity: f64,
+}
+
+/// Check whether `candidate` is already in the vocabulary under a close
+/// spelling ("colour" next to "color"). An exact copy is a duplicate too.
+fn near_duplicate(candidate: &str, vocabulary: &[String], threshold: f64) -> NearDuplicate {
+    match best_match(candidate, vocabulary) {
+        Some(best) => NearDuplicate {
+            is_duplicate: best.similarity >= threshold,
+            most_similar: Some(best.word),
+            similarity: best.similarity,
+        },
+        None => NearDuplicate {
+            is_duplicate: false,
+            most_similar: None,
+            similarity: 0.0,
+        },
+    }
+}
+
+/// Warn before adding a word that is a near-duplicate of one in the
+/// vocabulary, returning `{ is_duplicate, most_similar, similarity }`
+#[wasm_bindgen]
+pub fn is_near_duplicate(
+    candidate: &str,
+    vocabulary: JsValue,
+    threshold: f64,
+) -> Result<JsValue, JsValue> {
+    let vocab_vec: Vec<String> = serde_wasm_bindgen::from_value(vocabulary)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse vocabulary: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&near_duplicate(candidate, &vocab_vec, threshold))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+/// Score how well `abbrev` abbreviates `full` (case-insensitive).
+/// An acronym of the space-separated words scores 1.0, a prefix scores
+/// 0.6 to 1.0 and a subsequence 0.3 to 0.7, both scaled by how much of
+/// `full` the abbreviation covers. Anything else scores 0.0.
+fn abbreviation_similarity(abbrev: &str, full: &str) -> f64 {
+    let abbrev: Vec<char> = abbrev.to_lowercase().chars().collect();
+    let full_lower = full.to_lowercase();
+    let full_chars: Vec<char> = full_lower.chars().collect();
+
+    if abbrev.is_empty() || full_chars.is_empty() {
+        return 0.0;
+    }
+
+    let initials: Vec<char> = full_lower
+        .split_whitespace()
+        .filter_map(|w| w.chars().next())
+        .collect();
+    if initials.len() > 1 && initials == abbrev {
+        return 1.0;
+    }
+
+    let coverage = abbrev.len() as f64 / full_chars.len() as f64;
+    if full_chars.starts_with(&abbrev) {
+        return 0.6 + 0.4 * coverage;
+    }
+
+    let mut remaining = abbrev.iter().peekable();
+    for c in &full_chars {
+        if remaining.peek() == Some(&c) {
+            remaining.next();
+        }
+    }
+    if remaining.peek().is_none() {
+        return 0.3 + 0.4 * coverage;
+    }
+
+    0.0
+}
+
+/// Score every word as an expansion of `abbrev`, keeping non-zero scores
+/// sorted by score (descending).
+fn abbreviation_matches(abbrev: &str, words: &[String]) -> Vec<MatchResult> {
+    let mut matches: Vec<MatchResult> = words
+        .iter()
+        .map(|word| MatchResult::from_pair_with_algorithm(abbrev, word, Algorithm::Abbreviation))
+        .filter(|m| m.similarity > 0.0)
+        .collect();
+
+    matches.sort();
+    matches
+}
+
+/// Find words that `abbrev` could be an abbreviation of (prefix, acronym or subsequence)
+#[wasm_bindgen]
+pub fn find_abbreviation_matches(abbrev: &str, words: JsValue) -> Result<JsValue, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    let matches = abbreviation_matches(abbrev, &words_vec);
+
+    serde_wasm_bindgen::to_value(&matches)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+/// Pairwise similarity scores for a list of words (row `i`, column `j`).
+fn build_similarity_matrix(words: &[String]) -> Vec<Vec<f64>> {
+    words
+        .iter()
+        .map(|a| words.iter().map(|b| similarity_score(a, b)).collect())
+        .collect()
+}
+
+/// Compute the full pairwise similarity matrix for a list of words
+#[wasm_bindgen]
+pub fn similarity_matrix(words: JsValue) -> Result<JsValue, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&build_similarity_matrix(&words_vec))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize matrix: {}", e)))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NetworkEdge {
+    /// Index of the first word; always less than `target`
+    pub source: usize,
+    pub target: usize,
+    pub weight: f64,
+}
+
+/// Undirected edges between every pair of distinct words whose similarity
+/// reaches `threshold`, weighted by that similarity.
+fn build_confusion_network(words: &[String], threshold: f64) -> Vec<NetworkEdge> {
+    let mut edges = Vec::new();
+    for i in 0..words.len() {
+        for j in (i + 1)..words.len() {
+            let weight = similarity_score(&words[i], &words[j]);
+            if weight >= threshold {
+                edges.push(NetworkEdge {
+                    source: i,
+                    target: j,
+                    weight,
+                });
+            }
+        }
+    }
+    edges
+}
+
+/// Graph of confusable words as index-based `{ source, target, weight }` edges
+#[wasm_bindgen]
+pub fn confusion_network(words: JsValue, threshold: f64) -> Result<JsValue, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&build_confusion_network(&words_vec, threshold))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize edges: {}", e)))
+}
+
+/// Greedy farthest-first traversal of the confusion graph: starting from
+/// `seed`, repeatedly pick the word whose highest similarity to the words
+/// already picked is lowest (ties go to the earlier word). Returns indices.
+fn farthest_first_order(words: &[String], seed: &[usize]) -> Vec<usize> {
+    let mut order: Vec<usize> = seed.to_vec();
+    let mut closest = vec![f64::NEG_INFINITY; words.len()];
+    let mut picked = vec![false; words.len()];
+    for &i in seed {
+        picked[i] = true;
+    }
+    for (j, word) in words.iter().enumerate() {
+        for &i in seed {
+            closest[j] = closest[j].max(similarity_score(&words[i], word));
+        }
+    }
+
+    while order.len() < words.len() {
+        let next = (0..words.len())
+            .filter(|&j| !picked[j])
+            .min_by(|&a, &b| closest[a].total_cmp(&closest[b]).then(a.cmp(&b)))
+            .unwrap_or(0);
+        picked[next] = true;
+        order.push(next);
+        for (j, word) in words.iter().enumerate() {
+            closest[j] = closest[j].max(similarity_score(&words[next], word));
+        }
+    }
+    order
+}
+
+/// Order words so each new one is as unlike the earlier ones as possible,
+/// keeping the first word in place.
+fn learning_curve_order(words: &[String]) -> Vec<String> {
+    if words.is_empty() {
+        return Vec::new();
+    }
+    farthest_first_order(words, &[0])
+        .into_iter()
+        .map(|i| words[i].clone())
+        .collect()
+}
+
+/// Reorder vocabulary for acquisition: each next word has the lowest
+/// maximum similarity to the words introduced before it
+#[wasm_bindgen]
+pub fn reorder_for_learning_curve(words: JsValue) -> Result<JsValue, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&learning_curve_order(&words_vec))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+/// Order words so no two in a row are easily confused: start with the least
+/// similar pair (earliest on ties), then continue the farthest-first traversal.
+fn confusion_risk_order(words: &[String]) -> Vec<String> {
+    if words.is_empty() {
+        return Vec::new();
+    }
+    let mut seed = vec![0];
+    let mut lowest = f64::INFINITY;
+    for i in 0..words.len() {
+        for j in i + 1..words.len() {
+            let similarity = similarity_score(&words[i], &words[j]);
+            if similarity < lowest {
+                lowest = similarity;
+                seed = vec![i, j];
+            }
+        }
+    }
+    farthest_first_order(words, &seed)
+        .into_iter()
+        .map(|i| words[i].clone())
+        .collect()
+}
+
+/// Reorder vocabulary so consecutive words interfere as little as possible
+/// with each other
+#[wasm_bindgen]
+pub fn sort_by_confusion_risk(words: JsValue) -> Result<JsValue, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&confusion_risk_order(&words_vec))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+/// Similarity matrix that grows one word at a time, computing only the
+/// new row and column on each insertion
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct WasmSimilarityMatrix {
+    words: Vec<String>,
+    rows: Vec<Vec<f64>>,
+}
+
+#[wasm_bindgen]
+impl WasmSimilarityMatrix {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmSimilarityMatrix {
+        WasmSimilarityMatrix::default()
+    }
+
+    /// Append a word, filling in its row and column
+    pub fn add_word(&mut self, word: &str) {
+        let mut row: Vec<f64> = self
+            .words
+            .iter()
+            .map(|other| similarity_score(word, other))
+            .collect();
+        for (existing, &score) in self.rows.iter_mut().zip(&row) {
+            existing.push(score);
+        }
+        row.push(similarity_score(word, word));
+
+        self.words.push(word.to_string());
+        self.rows.push(row);
+    }
+
+    /// Remove the word at `index`, returning false if it is out of bounds
+    pub fn remove_word(&mut self, index: usize) -> bool {
+        if index >= self.words.len() {
+            return false;
+        }
+        self.words.remove(index);
+        self.rows.remove(index);
+        for row in &mut self.rows {
+            row.remove(index);
+        }
+        true
+    }
+
+    /// Similarity between word `i` and word `j`, NaN if either is out of bounds
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        self.rows
+            .get(i)
+            .and_then(|row| row.get(j))
+            .copied()
+            .unwrap_or(f64::NAN)
+    }
+
+    /// Number of words in the matrix
+    pub fn size(&self) -> usize {
+        self.words.len()
+    }
+
+    /// All scores in row-major order (`size * size` entries)
+    pub fn to_flat_array(&self) -> Vec<f64> {
+        self.rows.iter().flatten().copied().collect()
+    }
+}
+
+/// Edit distance with affine gap penalties (Gotoh's algorithm).
+/// Substitutions cost 1.0 while a run of `k` consecutive insertions or
+/// deletions costs `gap_open + (k - 1) * gap_extend`, so one long gap is
+/// cheaper than several scattered ones.
+fn affine_gap_levenshtein(s1: &str, s2: &str, gap_open: f64, gap_extend: f64) -> f64 {
+    let a: Vec<char> = s1.chars().collect();
+    let b: Vec<char> = s2.chars().collect();
+    let (n, m) = (a.len(), b.len());
+    let gap = |len: usize| {
+        if len == 0 {
+            0.0
+        } else {
+            gap_open + (len - 1) as f64 * gap_extend
+        }
+    };
+
+    // m_: last column aligns two characters, x: gap in s2 (deletion), y: gap in s1 (insertion)
+    let inf = f64::INFINITY;
+    let mut m_ = vec![vec![inf; m + 1]; n + 1];
+    let mut x = vec![vec![inf; m + 1]; n + 1];
+    let mut y = vec![vec![inf; m + 1]; n + 1];
+    m_[0][0] = 0.0;
+    for (i, row) in x.iter_mut().enumerate() {
+        row[0] = gap(i);
+    }
+    for (j, cell) in y[0].iter_mut().enumerate() {
+        *cell = gap(j);
+    }
+
+    for i in 1..=n {
+        for j in 1..=m {
+            let cost = if a[i - 1] == b[j - 1] { 0.0 } else { 1.0 };
+            m_[i][j] = m_[i - 1][j - 1].min(x[i - 1][j - 1]).min(y[i - 1][j - 1]) + cost;
+            x[i][j] = (m_[i - 1][j] + gap_open)
+                .min(x[i - 1][j] + gap_extend)
+                .min(y[i - 1][j] + gap_open);
+            y[i][j] = (m_[i][j - 1] + gap_open)
+                .min(y[i][j - 1] + gap_extend)
+                .min(x[i][j - 1] + gap_open);
+        }
+    }
+
+    m_[n][m].min(x[n][m]).min(y[n][m])
+}
+
+/// Calculate edit distance with affine gap penalties (WASM export)
+#[wasm_bindgen]
+pub fn calculate_affine_distance(s1: &str, s2: &str, gap_open: f64, gap_extend: f64) -> f64 {
+    affine_gap_levenshtein(s1, s2, gap_open, gap_extend)
+}
+
+/// Number of occurrences of each character in a string.
+fn char_histogram(s: &str) -> HashMap<char, usize> {
+    let mut histogram = HashMap::new();
+    for c in s.chars() {
+        *histogram.entry(c).or_insert(0) += 1;
+    }
+    histogram
+}
+
+/// Similarity (0.0 to 1.0) of the character frequency profiles of two strings,
+/// computed as `1 - L1 / 2` over the normalized histograms. Anagrams score 1.0
+/// and strings without a shared character score 0.0.
+fn char_frequency_similarity(s1: &str, s2: &str) -> f64 {
+    let n1 = s1.chars().count();
+    let n2 = s2.chars().count();
+    if n1 == 0 || n2 == 0 {
+        return if n1 == n2 { 1.0 } else { 0.0 };
+    }
+    let h1 = char_histogram(s1);
+    let h2 = char_histogram(s2);
+
+    // Scale both histograms by n1 * n2 so the L1 sum stays in integers
+    let count = |h: &HashMap<char, usize>, c: &char| h.get(c).copied().unwrap_or(0);
+    let scaled_l1: usize = h1
+        .keys()
+        .chain(h2.keys().filter(|c| !h1.contains_key(c)))
+        .map(|c| (count(&h1, c) * n2).abs_diff(count(&h2, c) * n1))
+        .sum();
+
+    1.0 - scaled_l1 as f64 / (2 * n1 * n2) as f64
+}
+
+/// Compare the character frequency histograms of two strings (WASM export)
+#[wasm_bindgen]
+pub fn calculate_frequency_histogram_similarity(s1: &str, s2: &str) -> f64 {
+    char_frequency_similarity(s1, s2)
+}
+
+/// Jaro similarity (0.0 to 1.0): the share of characters matching within a
+/// sliding window, penalized by transpositions.
+fn jaro_similarity(s1: &str, s2: &str) -> f64 {
+    let a: Vec<char> = s1.chars().collect();
+    let b: Vec<char> = s2.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0;
+    for (i, &c) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+        for j in start..end {
+            if !b_matched[j] && b[j] == c {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let a_seq = a.iter().zip(&a_matched).filter(|(_, &m)| m).map(|(c, _)| c);
+    let b_seq = b.iter().zip(&b_matched).filter(|(_, &m)| m).map(|(c, _)| c);
+    let transpositions = a_seq.zip(b_seq).filter(|(x, y)| x != y).count() / 2;
+
+    let m = matches as f64;
+    (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions as f64) / m) / 3.0
+}
+
+/// Blend of phonetic and spelling similarity:
+/// `phonetic_weight * jaro(metaphone(s1), metaphone(s2)) + (1 - phonetic_weight) * similarity_score(s1, s2)`.
+/// The weight must lie in [0, 1].
+fn combined_similarity(s1: &str, s2: &str, phonetic_weight: f64) -> Result<f64, String> {
+    if !(0.0..=1.0).contains(&phonetic_weight) {
+        return Err(format!(
+            "phonetic_weight must be between 0.0 and 1.0, got {}",
+            phonetic_weight
+        ));
+    }
+    let phonetic = jaro_similarity(&phonetic::metaphone(s1), &phonetic::metaphone(s2));
+    let spelling = similarity_score(s1, s2);
+    Ok(phonetic_weight * phonetic + (1.0 - phonetic_weight) * spelling)
+}
+
+/// Calculate a weighted blend of phonetic (Metaphone + Jaro) and Levenshtein similarity
+#[wasm_bindgen]
+pub fn calculate_combined_similarity(
+    s1: &str,
+    s2: &str,
+    phonetic_weight: f64,
+) -> Result<f64, JsValue> {
+    combined_similarity(s1, s2, phonetic_weight).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Largest fraction by which context can raise a contextual similarity
+const CONTEXT_BONUS_WEIGHT: f64 = 0.2;
+
+/// Similarity of an answer to the expected word, boosted when the answer
+/// resembles one of the neighbouring context words (the learner picked a
+/// word that fits the phrase). The boost factor is
+/// `1 + CONTEXT_BONUS_WEIGHT * max(similarity_score(user_word, c))` and the
+/// result is capped at 1.0, so it is never below the base score.
+fn contextual_similarity(user_word: &str, correct_word: &str, context: &[&str]) -> f64 {
+    let base = similarity_score(user_word, correct_word);
+    let closest_context = context
+        .iter()
+        .map(|c| similarity_score(user_word, c))
+        .fold(0.0, f64::max);
+    let context_bonus = 1.0 + CONTEXT_BONUS_WEIGHT * closest_context;
+    (base * context_bonus).min(1.0).max(base)
+}
+
+/// Calculate similarity of an answer, boosted by an array of surrounding context words
+#[wasm_bindgen]
+pub fn calculate_contextual_similarity(
+    user_word: &str,
+    correct_word: &str,
+    context: JsValue,
+) -> Result<f64, JsValue> {
+    let context_vec: Vec<String> = serde_wasm_bindgen::from_value(context)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse context: {}", e)))?;
+    let context_refs: Vec<&str> = context_vec.iter().map(String::as_str).collect();
+
+    Ok(contextual_similarity(
+        user_word,
+        correct_word,
+        &context_refs,
+    ))
+}
+
+/// Similarity after removing every character of `ignore_set` from both strings.
+fn similarity_ignoring_chars(s1: &str, s2: &str, ignore_set: &[char]) -> f64 {
+    let strip = |s: &str| -> String { s.chars().filter(|c| !ignore_set.contains(c)).collect() };
+    similarity_score(&strip(s1), &strip(s2))
+}
+
+/// Calculate similarity ignoring the characters listed in `ignore_chars`
+/// (e.g. `"'-"` to ignore apostrophes and hyphens)
+#[wasm_bindgen]
+pub fn calculate_similarity_ignore_chars(s1: &str, s2: &str, ignore_chars: &str) -> f64 {
+    let ignore_set: Vec<char> = ignore_chars.chars().collect();
+    similarity_ignoring_chars(s1, s2, &ignore_set)
+}
+
+/// Hamming distance after right-padding the shorter string with `'\0'`, so
+/// each missing trailing char counts as one mismatch (for fixed-field codes)
+#[wasm_bindgen]
+pub fn hamming_distance_padded(s1: &str, s2: &str) -> usize {
+    let a: Vec<char> = s1.chars().collect();
+    let b: Vec<char> = s2.chars().collect();
+    (0..a.len().max(b.len()))
+        .filter(|&i| a.get(i).unwrap_or(&'\0') != b.get(i).unwrap_or(&'\0'))
+        .count()
+}
+
+/// Whether `c` is a vowel letter (plain or accented Latin) or an Arabic short
+/// vowel mark, i.e. something a consonant skeleton drops
+fn is_skeleton_vowel(c: char) -> bool {
+    matches!(
+        c.to_lowercase().next().unwrap_or(c),
+        'a' | 'e'
+            | 'i'
+            | 'o'
+            | 'u'
+            | 'à'..='å'
+            | 'è'..='ï'
+            | 'ò'..='ö'
+            | 'ù'..='ü'
+            | '\u{064B}'..='\u{0652}'
+    )
+}
+
+/// Strip the vowels from a word, leaving its consonant skeleton
+/// ("written" → "wrttn"), the way Semitic roots are written
+fn consonant_skeleton(s: &str) -> String {
+    s.chars().filter(|&c| !is_skeleton_vowel(c)).collect()
+}
+
+/// `similarity_score` of the consonant skeletons
+fn consonant_skeleton_similarity(s1: &str, s2: &str) -> f64 {
+    similarity_score(&consonant_skeleton(s1), &consonant_skeleton(s2))
+}
+
+/// Calculate similarity of two words' consonant roots, ignoring vowels
+#[wasm_bindgen]
+pub fn calculate_root_similarity(s1: &str, s2: &str) -> f64 {
+    consonant_skeleton_similarity(s1, s2)
+}
+
+/// Dice coefficient of the whitespace-separated tokens, counting repeated
+/// tokens as many times as both sides share them, so word order doesn't
+/// matter. Two blank strings are identical.
+fn token_overlap_similarity(s1: &str, s2: &str) -> f64 {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    let mut total = 0;
+    for token in s1.split_whitespace() {
+        *counts.entry(token).or_insert(0) += 1;
+        total += 1;
+    }
+
+    let mut shared = 0;
+    for token in s2.split_whitespace() {
+        total += 1;
+        if let Some(count) = counts.get_mut(token).filter(|c| **c > 0) {
+            *count -= 1;
+            shared += 1;
+        }
+    }
+
+    if total == 0 {
+        return 1.0;
+    }
+    2.0 * shared as f64 / total as f64
+}
+
+/// Calculate how many words two sentences share, ignoring their order
+#[wasm_bindgen]
+pub fn calculate_token_overlap(s1: &str, s2: &str) -> f64 {
+    token_overlap_similarity(s1, s2)
+}
+
+/// Owned copies of `list`, for building word lists in tests
+#[cfg(test)]
+pub(crate) fn words(list: &[&str]) -> Vec<String> {
+    list.iter().map(|w| w.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein_distance() {
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(levenshtein_distance("", "test"), 4);
+        assert_eq!(levenshtein_distance("test", ""), 4);
+        assert_eq!(levenshtein_distance("same", "same"), 0);
+    }
+
+    #[test]
+    fn test_similarity_score() {
+        assert_eq!(similarity_score("same", "same"), 1.0);
+        assert!(similarity_score("test", "tost") > 0.7);
+        assert!(similarity_score("hello", "world") < 0.5);
+    }
+
+    #[test]
+    fn test_is_similar() {
+        assert!(is_similar("hello", "hallo", 0.8));
+        assert!(!is_similar("hello", "world", 0.8));
+    }
+
+    #[test]
+    fn test_match_words_exclude_exact() {
+        let vocab = words(&["cat", "bat", "hat"]);
+        let mut options = CompareOptions {
+            threshold: 0.5,
+            exclude_exact: true,
+        };
+
+        let found: Vec<String> = match_words("cat", &vocab, &options)
+            .into_iter()
+            .map(|m| m.word)
+            .collect();
+        assert_eq!(found, vec!["bat", "hat"]);
+
+        options.exclude_exact = false;
+        let found = match_words("cat", &vocab, &options);
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].word, "cat");
+    }
+
+    #[test]
+    fn test_correct_word() {
+        let vocab = words(&["receive", "perceive", "achieve", "review"]);
+        let correction = correct_word("recieve", &vocab).unwrap();
+        assert_eq!(correction.corrected, "receive");
+        assert!(correction.confidence > 0.5 && correction.confidence < 1.0);
+
+        let exact = correct_word("achieve", &vocab).unwrap();
+        assert_eq!(exact.corrected, "achieve");
+        assert_eq!(exact.confidence, 1.0);
+    }
+
+    #[test]
+    fn test_correct_word_no_candidate() {
+        assert!(correct_word("recieve", &[]).is_none());
+        assert!(correct_word("xyz", &words(&["receive"])).is_none());
+    }
+
+    #[test]
+    fn test_correct_words_preserves_order() {
+        let vocab = words(&["receive", "achieve", "separate"]);
+        let typos = words(&["seperate", "recieve", "achieve"]);
+        let corrections = correct_words(&typos, &vocab);
+
+        let originals: Vec<&str> = corrections.iter().map(|c| c.original.as_str()).collect();
+        assert_eq!(originals, vec!["seperate", "recieve", "achieve"]);
+        let corrected: Vec<&str> = corrections.iter().map(|c| c.corrected.as_str()).collect();
+        assert_eq!(corrected, vec!["separate", "receive", "achieve"]);
+        assert_eq!(corrections[2].confidence, 1.0);
+    }
+
+    #[test]
+    fn test_correct_words_empty() {
+        assert!(correct_words(&[], &words(&["receive"])).is_empty());
+    }
+
+    #[test]
+    fn test_tokenize_words() {
+        assert_eq!(
+            tokenize_words("the quick  fox"),
+            vec!["the", "quick", "fox"]
+        );
+        assert_eq!(tokenize_words("Hello, world!"), vec!["Hello", "world"]);
+        assert!(tokenize_words("... -- !?").is_empty());
+    }
+
+    #[test]
+    fn test_tokenize_words_cjk() {
+        let text = "東京タワー";
+        let whitespace: Vec<&str> = text.split_whitespace().collect();
+        assert_eq!(whitespace.len(), 1);
+        assert!(tokenize_words(text).len() > 1);
+    }
+
+    #[test]
+    fn test_deduplicate_words_merges_variants() {
+        let vocab = words(&["colour", "cat", "color", "bat"]);
+        let result = deduplicate_words(&vocab, 0.8);
+
+        assert_eq!(result.words, vec!["cat", "color", "bat"]);
+        assert_eq!(result.merges.len(), 1);
+        assert_eq!(result.merges[0].removed, "colour");
+        assert_eq!(result.merges[0].kept_as, "color");
+    }
+
+    #[test]
+    fn test_deduplicate_words_keeps_distinct() {
+        let vocab = words(&["cat", "bat", "hat", "grey", "gray", "dog"]);
+        let result = deduplicate_words(&vocab, 0.9);
+        assert_eq!(result.words.len(), vocab.len());
+        assert!(result.merges.is_empty());
+
+        let result = deduplicate_words(&vocab, 0.6);
+        for (i, a) in result.words.iter().enumerate() {
+            for b in &result.words[i + 1..] {
+                assert!(similarity_score(a, b) < 0.6, "{} / {}", a, b);
+            }
+        }
+        assert_eq!(result.words.len() + result.merges.len(), vocab.len());
+    }
+
+    #[test]
+    fn test_abbreviation_similarity() {
+        let prefix = abbreviation_similarity("info", "information");
+        assert!(prefix > 0.6 && prefix < 1.0);
+        assert!(prefix > similarity_score("info", "information"));
+
+        assert_eq!(abbreviation_similarity("UN", "United Nations"), 1.0);
+
+        let subsequence = abbreviation_similarity("prf", "prefix");
+        assert!(subsequence > 0.0 && subsequence < prefix);
+
+        assert_eq!(abbreviation_similarity("xyz", "prefix"), 0.0);
+        assert_eq!(abbreviation_similarity("", "prefix"), 0.0);
+    }
+
+    #[test]
+    fn test_abbreviation_matches() {
+        let vocab = words(&["prefix", "information", "United Nations", "cat"]);
+        let found: Vec<String> = abbreviation_matches("un", &vocab)
+            .into_iter()
+            .map(|m| m.word)
+            .collect();
+        assert_eq!(found, vec!["United Nations"]);
+
+        let found: Vec<String> = abbreviation_matches("pi", &vocab)
+            .into_iter()
+            .map(|m| m.word)
+            .collect();
+        assert_eq!(found, vec!["prefix"]);
+    }
+
+    #[test]
+    fn test_incremental_matrix_matches_full() {
+        let vocab = words(&["kitten", "sitting", "mitten"]);
+        let mut matrix = WasmSimilarityMatrix::new();
+        for word in &vocab {
+            matrix.add_word(word);
+        }
+
+        let full = build_similarity_matrix(&vocab);
+        assert_eq!(matrix.size(), 3);
+        assert_eq!(matrix.to_flat_array(), full.concat());
+        assert_eq!(matrix.get(0, 2), full[0][2]);
+        assert!(matrix.get(3, 0).is_nan());
+    }
+
+    #[test]
+    fn test_incremental_matrix_remove_and_readd() {
+        let mut matrix = WasmSimilarityMatrix::new();
+        for word in ["kitten", "sitting", "mitten"] {
+            matrix.add_word(word);
+        }
+        let before = matrix.to_flat_array();
+
+        assert!(matrix.remove_word(2));
+        assert_eq!(matrix.size(), 2);
+        matrix.add_word("mitten");
+        assert_eq!(matrix.to_flat_array(), before);
+
+        assert!(!matrix.remove_word(5));
+    }
+
+    #[test]
+    fn test_match_result_ordering() {
+        let vocab = words(&["hat", "cart", "dog", "bat", "cat"]);
+        let expected: Vec<String> = match_words("cat", &vocab, &CompareOptions::default())
+            .into_iter()
+            .map(|m| m.word)
+            .collect();
+
+        let mut results: Vec<MatchResult> = vocab
+            .iter()
+            .map(|w| MatchResult::from_pair("cat", w))
+            .collect();
+        results.sort();
+        let sorted: Vec<String> = results.into_iter().map(|m| m.word).collect();
+
+        assert_eq!(sorted, expected);
+        assert_eq!(sorted[..4], ["cat", "cart", "bat", "hat"]);
+    }
+
+    #[test]
+    fn test_match_result_btreeset() {
+        let mut set = std::collections::BTreeSet::new();
+        set.insert(MatchResult::from_pair("cat", "bat"));
+        set.insert(MatchResult::from_pair("cat", "hat"));
+        set.insert(MatchResult::from_pair("cat", "bat"));
+
+        assert_eq!(set.len(), 2);
+        assert_eq!(
+            MatchResult::from_pair("cat", "bat"),
+            MatchResult::from_pair("cat", "bat")
+        );
+        assert!(MatchResult::from_pair("cat", "cat") < MatchResult::from_pair("cat", "bat"));
+    }
+
+    #[test]
+    fn test_match_result_from_pair() {
+        let exact = MatchResult::from_pair("a", "a");
+        assert!(exact.is_exact);
+        assert_eq!(exact.distance, 0);
+        assert_eq!(exact.similarity, 1.0);
+
+        let m = MatchResult::from_pair("kitten", "sitting");
+        assert!(!m.is_exact);
+        assert_eq!(m.word, "sitting");
+        assert_eq!(m.distance, levenshtein_distance("kitten", "sitting"));
+        assert_eq!(m.similarity, 1.0 - 3.0 / 7.0);
+    }
+
+    #[test]
+    fn test_match_result_from_pair_with_algorithm() {
+        let phonetic = MatchResult::from_pair_with_algorithm(
+            "knight",
+            "night",
+            Algorithm::Phonetic(PhoneticAlgorithm::Metaphone),
+        );
+        assert_eq!(phonetic.distance, 0);
+        assert_eq!(phonetic.similarity, 1.0);
+        assert!(!phonetic.is_exact);
+
+        let abbrev =
+            MatchResult::from_pair_with_algorithm("UN", "United Nations", Algorithm::Abbreviation);
+        assert_eq!(abbrev.similarity, 1.0);
+        assert_eq!(
+            abbrev.distance,
+            levenshtein_distance("UN", "United Nations")
+        );
+    }
+
+    #[test]
+    fn test_affine_gap_levenshtein() {
+        let (open, extend) = (1.0, 0.25);
+        // "cde" is removed as a single gap
+        assert_eq!(
+            affine_gap_levenshtein("abcdef", "abf", open, extend),
+            open + 2.0 * extend
+        );
+        assert!(affine_gap_levenshtein("abcdef", "abf", open, extend) < 3.0 * (open + extend));
+        assert_eq!(
+            affine_gap_levenshtein("abf", "abcdef", open, extend),
+            open + 2.0 * extend
+        );
+
+        assert_eq!(affine_gap_levenshtein("same", "same", open, extend), 0.0);
+        assert_eq!(affine_gap_levenshtein("cat", "bat", open, extend), 1.0);
+        assert_eq!(affine_gap_levenshtein("", "", open, extend), 0.0);
+        assert_eq!(
+            affine_gap_levenshtein("", "abc", open, extend),
+            open + 2.0 * extend
+        );
+    }
+
+    #[test]
+    fn test_affine_matches_levenshtein_with_unit_gaps() {
+        for (a, b) in [("kitten", "sitting"), ("flaw", "lawn"), ("", "test")] {
+            assert_eq!(
+                affine_gap_levenshtein(a, b, 1.0, 1.0),
+                levenshtein_distance(a, b) as f64
+            );
+        }
+    }
+
+    #[test]
+    fn test_char_frequency_similarity() {
+        assert_eq!(char_frequency_similarity("listen", "silent"), 1.0);
+        assert_eq!(char_frequency_similarity("abc", "xyz"), 0.0);
+        assert_eq!(char_frequency_similarity("", ""), 1.0);
+        assert_eq!(char_frequency_similarity("abc", ""), 0.0);
+
+        let partial = char_frequency_similarity("hello", "help");
+        assert!(partial > 0.0 && partial < 1.0);
+    }
+
+    #[test]
+    fn test_char_frequency_similarity_symmetric() {
+        for (a, b) in [
+            ("hello", "help"),
+            ("banana", "bandana"),
+            ("kitten", "sitting"),
+        ] {
+            assert!(
+                (char_frequency_similarity(a, b) - char_frequency_similarity(b, a)).abs() < 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_jaro_similarity() {
+        assert!((jaro_similarity("MARTHA", "MARHTA") - 0.944).abs() < 1e-3);
+        assert!((jaro_similarity("DIXON", "DICKSONX") - 0.767).abs() < 1e-3);
+        assert_eq!(jaro_similarity("same", "same"), 1.0);
+        assert_eq!(jaro_similarity("abc", "xyz"), 0.0);
+        assert_eq!(jaro_similarity("", ""), 1.0);
+    }
+
+    #[test]
+    fn test_combined_similarity() {
+        for (a, b) in [("knight", "night"), ("cat", "bat"), ("phone", "fone")] {
+            assert_eq!(
+                combined_similarity(a, b, 0.0).unwrap(),
+                similarity_score(a, b)
+            );
+            let phonetic = jaro_similarity(&phonetic::metaphone(a), &phonetic::metaphone(b));
+            assert_eq!(combined_similarity(a, b, 1.0).unwrap(), phonetic);
+        }
+        assert!(
+            combined_similarity("knight", "night", 0.5).unwrap()
+                > similarity_score("knight", "night")
+        );
+    }
+
+    #[test]
+    fn test_combined_similarity_invalid_weight() {
+        assert!(combined_similarity("cat", "bat", -0.1).is_err());
+        assert!(combined_similarity("cat", "bat", 1.5).is_err());
+        assert!(combined_similarity("cat", "bat", f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_top_n_matches() {
+        let vocab = words(&["cat", "cart", "cast", "cats", "dog", "ca"]);
+        let top = top_n_matches("cat", &vocab, 2, 0.6, 0.95);
+        assert_eq!(top.len(), 2);
+        assert!(top.iter().all(|m| m.word != "cat"));
+        assert!(top[0].similarity >= top[1].similarity);
+
+        assert!(top_n_matches("cat", &vocab, 0, 0.0, 1.0).is_empty());
+        assert!(top_n_matches("cat", &vocab, 10, 0.0, 1.0).len() == 5);
+    }
+
+    #[test]
+    fn test_confusion_network() {
+        let vocab = words(&["cat", "bat", "cart", "dog", "cat"]);
+        let edges = build_confusion_network(&vocab, 0.6);
+
+        assert!(edges.iter().all(|e| e.source < e.target));
+        assert!(edges.iter().all(|e| e.weight >= 0.6));
+        let pairs: Vec<(usize, usize)> = edges.iter().map(|e| (e.source, e.target)).collect();
+        assert!(pairs.contains(&(0, 1)));
+        assert!(pairs.contains(&(0, 4)));
+        assert!(!pairs.iter().any(|&(s, t)| s == 3 || t == 3));
+    }
+
+    #[test]
+    fn test_confusion_network_empty() {
+        assert!(build_confusion_network(&[], 0.5).is_empty());
+        assert!(build_confusion_network(&words(&["solo"]), 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_similarity_f32_precision() {
+        let long_a: String = "abcdefghij".repeat(10);
+        let long_b: String = "abcdefghik".repeat(10);
+        let pairs = [
+            ("kitten", "sitting"),
+            ("hello", "hallo"),
+            ("", ""),
+            (long_a.as_str(), long_b.as_str()),
+            (long_a.as_str(), "abc"),
+        ];
+        for (a, b) in pairs {
+            let diff = (calculate_similarity_f32(a, b) as f64 - similarity_score(a, b)).abs();
+            assert!(diff < 1e-6, "{} vs {}: {}", a, b, diff);
+        }
+    }
+
+    #[test]
+    fn test_match_words_f32_threshold() {
+        let vocab = words(&["cat", "cart", "bat", "dog"]);
+        let threshold = 0.75f32;
+        let matches = match_words_f32("cat", &vocab, threshold);
+        assert!(matches.iter().all(|m| m.similarity >= threshold));
+
+        let options = CompareOptions {
+            threshold: threshold as f64,
+            ..CompareOptions::default()
+        };
+        let expected: Vec<String> = match_words("cat", &vocab, &options)
+            .into_iter()
+            .map(|m| m.word)
+            .collect();
+        let found: Vec<String> = matches.into_iter().map(|m| m.word).collect();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_bulk_scores_match_find_matches() {
+        let vocab = words(&["cat", "cart", "bat", "dog", "cat"]);
+        let similarities = similarities_for("cat", &vocab);
+        let distances = distances_for("cat", &vocab);
+        assert_eq!(similarities.len(), vocab.len());
+        assert_eq!(distances.len(), vocab.len());
+
+        for m in match_words("cat", &vocab, &CompareOptions::default()) {
+            let i = vocab.iter().position(|w| *w == m.word).unwrap();
+            assert_eq!(similarities[i], m.similarity);
+            assert_eq!(distances[i] as usize, m.distance);
+        }
+        assert!(similarities_for("cat", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_null_delimited() {
+        let vocab = words(&["cat", "über", "cart"]);
+        let encoded = vocab.join("\0");
+        assert_eq!(parse_null_delimited(encoded.as_bytes()).unwrap(), vocab);
+        assert_eq!(
+            parse_null_delimited(b"cat\0cart\0").unwrap(),
+            words(&["cat", "cart"])
+        );
+        assert!(parse_null_delimited(b"").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_null_delimited_boundaries() {
+        // A NUL inside a word just splits it
+        assert_eq!(parse_null_delimited(b"ca\0t").unwrap(), words(&["ca", "t"]));
+        assert!(parse_null_delimited(&[0x63, 0xff, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_binary_input_matches_json_input() {
+        let vocab = words(&["cat", "cart", "bat", "dog"]);
+        let decoded = parse_null_delimited(vocab.join("\0").as_bytes()).unwrap();
+        let options = CompareOptions::default();
+        let from_binary: Vec<String> = match_words("cat", &decoded, &options)
+            .into_iter()
+            .map(|m| m.word)
+            .collect();
+        let from_list: Vec<String> = match_words("cat", &vocab, &options)
+            .into_iter()
+            .map(|m| m.word)
+            .collect();
+        assert_eq!(from_binary, from_list);
+    }
+
+    #[test]
+    fn test_match_words_empty_strings() {
+        let vocab = words(&["", "a", ""]);
+        let matches = match_words("", &vocab, &CompareOptions::default());
+        assert_eq!(matches.len(), 3);
+        assert!(matches.iter().all(|m| !m.similarity.is_nan()));
+        assert_eq!(matches[0].similarity, 1.0);
+        assert!(matches[0].is_exact);
+        assert_eq!(matches[2].word, "a");
+        assert_eq!(matches[2].similarity, 0.0);
+
+        assert_eq!(similarity_score("", ""), 1.0);
+        assert_eq!(best_match("", &vocab).unwrap().similarity, 1.0);
+    }
+
+    #[test]
+    fn test_contextual_similarity() {
+        let context = ["the", "river", "bank"];
+        for (user, correct) in [
+            ("banks", "bank"),
+            ("rivr", "river"),
+            ("xyz", "bank"),
+            ("a", ""),
+        ] {
+            let base = similarity_score(user, correct);
+            assert!(contextual_similarity(user, correct, &context) >= base);
+            assert!(contextual_similarity(user, correct, &context) <= 1.0);
+        }
+        assert!(
+            contextual_similarity("banks", "bunk", &context) > similarity_score("banks", "bunk")
+        );
+    }
+
+    #[test]
+    fn test_contextual_similarity_empty_context() {
+        assert_eq!(
+            contextual_similarity("house", "horse", &[]),
+            similarity_score("house", "horse")
+        );
+    }
+
+    #[test]
+    fn test_match_words_until_completes() {
+        let vocab = words(&["cat", "cart", "bat", "dog"]);
+        let timed = match_words_until("cat", &vocab, 0.5, 1_000.0, || 0.0);
+        assert!(timed.completed);
+        assert_eq!(timed.words_processed, vocab.len());
+
+        let options = CompareOptions {
+            threshold: 0.5,
+            ..CompareOptions::default()
+        };
+        let expected: Vec<String> = match_words("cat", &vocab, &options)
+            .into_iter()
+            .map(|m| m.word)
+            .collect();
+        let found: Vec<String> = timed.results.into_iter().map(|m| m.word).collect();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_match_words_until_deadline() {
+        let vocab: Vec<String> = (0..350).map(|i| format!("word{}", i)).collect();
+        let clock = std::cell::Cell::new(0.0);
+        // Time advances by 1 ms per deadline check
+        let timed = match_words_until("word1", &vocab, 0.0, 2.0, || {
+            clock.set(clock.get() + 1.0);
+            clock.get()
+        });
+        assert!(!timed.completed);
+        // Checks at 100 and 200 words are still in time, the one at 300 is not
+        assert_eq!(timed.words_processed, 300);
+        assert_eq!(timed.results.len(), 300);
+    }
+
+    #[test]
+    fn test_similarity_ignoring_chars() {
+        assert_eq!(calculate_similarity_ignore_chars("don't", "dont", "'"), 1.0);
+        assert_eq!(
+            calculate_similarity_ignore_chars("cat", "cut", "aeiou"),
+            1.0
+        );
+        assert_eq!(
+            calculate_similarity_ignore_chars("bead", "bd", "aeiou"),
+            1.0
+        );
+        assert_eq!(
+            calculate_similarity_ignore_chars("don't", "dont", ""),
+            similarity_score("don't", "dont")
+        );
+        // Whitespace still counts unless listed
+        assert!(calculate_similarity_ignore_chars("ice cream", "icecream", "'") < 1.0);
+    }
+
+    #[test]
+    fn test_learning_curve_order() {
+        let vocab = words(&["cat", "bat", "hat", "dog", "fish", "cart"]);
+        let order = learning_curve_order(&vocab);
+
+        assert_eq!(order[0], "cat");
+        let mut sorted = order.clone();
+        sorted.sort();
+        let mut expected = vocab.clone();
+        expected.sort();
+        assert_eq!(sorted, expected);
+
+        // Each pick is at most as confusable with earlier words as any remaining word
+        let max_sim = |w: &str, seen: &[String]| {
+            seen.iter()
+                .map(|s| similarity_score(s, w))
+                .fold(f64::NEG_INFINITY, f64::max)
+        };
+        for k in 1..order.len() {
+            let chosen = max_sim(&order[k], &order[..k]);
+            for alternative in &order[k + 1..] {
+                assert!(chosen <= max_sim(alternative, &order[..k]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_learning_curve_order_edge_cases() {
+        assert!(learning_curve_order(&[]).is_empty());
+        assert_eq!(learning_curve_order(&words(&["solo"])), vec!["solo"]);
+    }
+
+    #[test]
+    fn test_normalized_edit_distance() {
+        // Char and byte lengths agree on ASCII, so both variants invert
+        // `calculate_similarity` there
+        for (s1, s2) in [
+            ("kitten", "sitting"),
+            ("same", "same"),
+            ("abc", "xyz"),
+            ("a", ""),
+        ] {
+            let diff = normalized_edit_distance(s1, s2) - (1.0 - calculate_similarity(s1, s2));
+            assert!(diff.abs() < 1e-12, "{} vs {}", s1, s2);
+        }
+        assert_eq!(normalized_edit_distance("same", "same"), 0.0);
+        assert_eq!(normalized_edit_distance("abc", "xyz"), 1.0);
+        assert_eq!(normalized_edit_distance("", ""), 0.0);
+    }
+
+    #[test]
+    fn test_normalized_edit_distance_bytes() {
+        assert_eq!(
+            normalized_edit_distance_bytes("kitten", "sitting"),
+            normalized_edit_distance("kitten", "sitting")
+        );
+        // "café" is 4 chars but 5 bytes
+        assert_eq!(normalized_edit_distance("café", "cafe"), 0.25);
+        assert_eq!(normalized_edit_distance_bytes("café", "cafe"), 0.2);
+
+        for (s1, s2) in [("café", "cafe"), ("über", "uber"), ("kitten", "sitting")] {
+            let diff =
+                normalized_edit_distance_bytes(s1, s2) - (1.0 - calculate_similarity(s1, s2));
+            assert!(diff.abs() < 1e-12, "{} vs {}", s1, s2);
+        }
+        // `calculate_similarity` keeps its byte normalization
+        assert!((calculate_similarity("café", "cafe") - 0.8).abs() < 1e-12);
+    }
+
+    #[test]
+    #[ignore = "needs wasm-pack and the wasm32 target; run with `cargo test -- --ignored`"]
+    fn test_release_wasm_size() {
+        let out_dir = std::env::temp_dir().join("similarity-size-check");
+        let status = std::process::Command::new("wasm-pack")
+            .args(["build", "--release", "--target", "web", "--out-dir"])
+            .arg(&out_dir)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .status()
+            .expect("failed to run wasm-pack");
+        assert!(status.success());
+
+        let size = std::fs::metadata(out_dir.join("similarity_bg.wasm"))
+            .unwrap()
+            .len();
+        assert!(size < 200 * 1024, "similarity_bg.wasm is {} bytes", size);
+    }
+
+    /// Mirror of `unpackResults` in lib/packed-matches.ts
+    fn unpack_results(bytes: &[u8]) -> Vec<MatchResult> {
+        let u32_at = |pos: usize| u32::from_le_bytes(bytes[pos..pos + 4].try_into().unwrap());
+        let count = u32_at(0) as usize;
+        let mut pos = 4;
+        let mut results = Vec::with_capacity(count);
+        for _ in 0..count {
+            let len = u32_at(pos) as usize;
+            pos += 4;
+            let word = std::str::from_utf8(&bytes[pos..pos + len])
+                .unwrap()
+                .to_string();
+            pos += len;
+            let distance = u32_at(pos) as usize;
+            pos += 4;
+            let similarity = f64::from_le_bytes(bytes[pos..pos + 8].try_into().unwrap());
+            pos += 8;
+            results.push(MatchResult {
+                word,
+                distance,
+                similarity,
+                is_exact: distance == 0,
+            });
+        }
+        assert_eq!(pos, bytes.len());
+        results
+    }
+
+    #[test]
+    fn test_pack_results_round_trip() {
+        let vocab = words(&["hello", "héllo", "help", "world", "hello"]);
+        let matches = match_words("hello", &vocab, &CompareOptions::default());
+        assert!(!matches.is_empty());
+
+        let unpacked = unpack_results(&pack_results(&matches));
+        assert_eq!(unpacked.len(), matches.len());
+        for (a, b) in matches.iter().zip(&unpacked) {
+            assert_eq!(a.word, b.word);
+            assert_eq!(a.distance, b.distance);
+            assert_eq!(a.similarity.to_bits(), b.similarity.to_bits());
+            assert_eq!(a.is_exact, b.is_exact);
+        }
+
+        assert_eq!(pack_results(&[]), vec![0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_hamming_distance_padded() {
+        assert_eq!(hamming_distance_padded("abc", "ab"), 1);
+        assert_eq!(hamming_distance_padded("abc", "abcd"), 1);
+        assert_eq!(hamming_distance_padded("abc", "abc"), 0);
+        assert_eq!(hamming_distance_padded("abc", "xbz"), 2);
+        assert_eq!(hamming_distance_padded("", "ab"), 2);
+        // Positions shift, unlike edit distance: "bc" vs "abc" is 3
+        assert_eq!(hamming_distance_padded("bc", "abc"), 3);
+    }
+
+    #[test]
+    fn test_consonant_skeleton() {
+        assert_eq!(consonant_skeleton("write"), "wrt");
+        assert_eq!(consonant_skeleton("written"), "wrttn");
+        assert_eq!(consonant_skeleton("Éclair"), "clr");
+        // Arabic "kataba" with its short vowel marks
+        assert_eq!(consonant_skeleton("كَتَبَ"), "كتب");
+        assert_eq!(consonant_skeleton("aeiou"), "");
+    }
+
+    #[test]
+    fn test_consonant_skeleton_similarity() {
+        assert_eq!(calculate_root_similarity("aei", "aei"), 1.0);
+        assert_eq!(calculate_root_similarity("kitab", "kutub"), 1.0);
+        assert!(consonant_skeleton_similarity("kitab", "maktab") < 1.0);
+    }
+
+    #[test]
+    fn test_best_match_above() {
+        let vocab = words(&["apple", "banana", "cherry"]);
+        assert!(best_match_above("xyz", &vocab, 0.5).is_none());
+        assert!(best_match_above("aple", &[], 0.0).is_none());
+
+        let above = best_match_above("aple", &vocab, 0.5).unwrap();
+        let best = best_match("aple", &vocab).unwrap();
+        assert_eq!(above.word, best.word);
+        assert_eq!(above.similarity, best.similarity);
+        assert!(best_match_above("aple", &vocab, best.similarity).is_some());
+    }
+
+    #[test]
+    fn test_near_duplicate() {
+        let vocab = words(&["color", "dog", "bat"]);
+        let colour = near_duplicate("colour", &vocab, 0.75);
+        assert!(colour.is_duplicate);
+        assert_eq!(colour.most_similar.as_deref(), Some("color"));
+
+        let cat = near_duplicate("cat", &vocab, 0.9);
+        assert!(!cat.is_duplicate);
+        assert_eq!(cat.most_similar.as_deref(), Some("bat"));
+
+        let empty = near_duplicate("cat", &[], 0.0);
+        assert!(!empty.is_duplicate);
+        assert_eq!(empty.most_similar, None);
+    }
+
+    #[test]
+    fn test_token_overlap_similarity() {
+        assert_eq!(
+            calculate_token_overlap("the quick fox", "fox quick the"),
+            1.0
+        );
+        assert_eq!(calculate_token_overlap("the cat", "the dog"), 0.5);
+        assert_eq!(calculate_token_overlap("", ""), 1.0);
+        assert_eq!(token_overlap_similarity("the cat", ""), 0.0);
+        // "the" is shared once, not twice
+        assert_eq!(token_overlap_similarity("the the", "the cat"), 0.5);
+    }
+
+    #[test]
+    fn test_confusion_risk_order() {
+        let vocab = words(&["cat", "bat", "hat", "elephant", "cap", "zebra"]);
+        let ordered = confusion_risk_order(&vocab);
+
+        let mut sorted = ordered.clone();
+        sorted.sort();
+        let mut expected = vocab.clone();
+        expected.sort();
+        assert_eq!(sorted, expected);
+
+        let first_pair = similarity_score(&ordered[0], &ordered[1]);
+        for i in 0..vocab.len() {
+            for j in i + 1..vocab.len() {
+                assert!(first_pair <= similarity_score(&vocab[i], &vocab[j]));
+            }
+        }
+
+        assert!(confusion_risk_order(&[]).is_empty());
+        assert_eq!(confusion_risk_order(&vocab[..1]), vec!["cat"]);
+    }
+
+    #[test]
+    fn test_similarity_score_exact() {
+        for (s1, s2) in [
+            ("kitten", "sitting"),
+            ("abc", "abd"),
+            ("café", "cafe"),
+            ("a", "xyz"),
+        ] {
+            let (distance, max_len, similarity) = similarity_score_exact(s1, s2);
+            assert_eq!(1.0 - distance as f64 / max_len as f64, similarity);
+            assert_eq!(similarity, similarity_score(s1, s2));
+            assert_eq!(distance, levenshtein_distance(s1, s2));
+        }
+        assert_eq!(similarity_score_exact("", ""), (0, 0, 1.0));
+        assert_eq!(similarity_score_exact("café", "cafe").1, 5);
     }
 }
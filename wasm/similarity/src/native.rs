@@ -0,0 +1,54 @@
+//! Entry points for Rust callers that already hold their data in native
+//! form. Nothing here is exported to JS.
+
+/// Levenshtein distance counting whole tokens as the edit unit
+fn token_edit_distance(toks1: &[&str], toks2: &[&str]) -> usize {
+    let mut prev: Vec<usize> = (0..=toks2.len()).collect();
+    let mut curr = vec![0; toks2.len() + 1];
+
+    for (i, t1) in toks1.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, t2) in toks2.iter().enumerate() {
+            let cost = if t1 == t2 { 0 } else { 1 };
+            curr[j + 1] = (prev[j + 1] + 1).min(curr[j] + 1).min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[toks2.len()]
+}
+
+/// Similarity in [0, 1] of two pre-tokenized sequences: one minus the
+/// token-level edit distance over the longer sequence's token count.
+/// Two empty sequences are identical.
+pub fn token_sequence_similarity(toks1: &[&str], toks2: &[&str]) -> f64 {
+    let max_len = toks1.len().max(toks2.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - token_edit_distance(toks1, toks2) as f64 / max_len as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_sequence_similarity() {
+        assert_eq!(
+            token_sequence_similarity(&["the", "cat"], &["the", "cat"]),
+            1.0
+        );
+        assert!(token_sequence_similarity(&["a"], &["b", "c"]) < 1.0);
+        assert_eq!(token_sequence_similarity(&["a"], &["b", "c"]), 0.0);
+        let one_swap = token_sequence_similarity(&["the", "cat", "sat"], &["the", "dog", "sat"]);
+        assert!((one_swap - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(token_sequence_similarity(&[], &[]), 1.0);
+    }
+
+    #[test]
+    fn test_token_sequence_similarity_via_native_module() {
+        let similarity = crate::native::token_sequence_similarity(&["hello"], &["hello", "world"]);
+        assert_eq!(similarity, 0.5);
+    }
+}
@@ -0,0 +1,926 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::similarity_score;
+
+/// Phonetic encodings available for grouping words by sound
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PhoneticAlgorithm {
+    Soundex,
+    Metaphone,
+}
+
+impl PhoneticAlgorithm {
+    /// Parse an algorithm name as passed from JS (`"soundex"` or `"metaphone"`)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "soundex" => Some(PhoneticAlgorithm::Soundex),
+            "metaphone" => Some(PhoneticAlgorithm::Metaphone),
+            _ => None,
+        }
+    }
+
+    /// Encode a word with this algorithm
+    pub fn encode(&self, word: &str) -> String {
+        match self {
+            PhoneticAlgorithm::Soundex => soundex(word),
+            PhoneticAlgorithm::Metaphone => metaphone(word),
+        }
+    }
+}
+
+/// Uppercased ASCII letters of a word; everything else is ignored by the encoders.
+fn ascii_letters(word: &str) -> Vec<char> {
+    word.chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+fn is_vowel(c: Option<char>) -> bool {
+    matches!(c, Some('A' | 'E' | 'I' | 'O' | 'U'))
+}
+
+/// Soundex digit for an uppercase letter, '0' for vowels and ignored letters.
+fn soundex_digit(c: char) -> char {
+    match c {
+        'B' | 'F' | 'P' | 'V' => '1',
+        'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => '2',
+        'D' | 'T' => '3',
+        'L' => '4',
+        'M' | 'N' => '5',
+        'R' => '6',
+        _ => '0',
+    }
+}
+
+/// American Soundex: first letter followed by three digits (e.g. "Robert" -> "R163").
+/// Returns an empty string when the word has no ASCII letters.
+pub(crate) fn soundex(word: &str) -> String {
+    let letters = ascii_letters(word);
+    let Some(&first) = letters.first() else {
+        return String::new();
+    };
+
+    let mut code = String::from(first);
+    let mut last = soundex_digit(first);
+    for &c in &letters[1..] {
+        // H and W do not separate letters with the same code
+        if c == 'H' || c == 'W' {
+            continue;
+        }
+        let digit = soundex_digit(c);
+        if digit != '0' && digit != last {
+            code.push(digit);
+            if code.len() == 4 {
+                break;
+            }
+        }
+        last = digit;
+    }
+
+    while code.len() < 4 {
+        code.push('0');
+    }
+    code
+}
+
+/// Original Metaphone encoding by Lawrence Philips.
+/// `0` stands for the "th" sound and `X` for "sh"/"ch".
+pub(crate) fn metaphone(word: &str) -> String {
+    let w = ascii_letters(word);
+    if w.is_empty() {
+        return String::new();
+    }
+    let at = |i: usize| w.get(i).copied();
+
+    let mut code = String::new();
+    let mut i = 0;
+
+    // Initial letter exceptions
+    match (w[0], at(1)) {
+        ('K' | 'G' | 'P', Some('N')) | ('A', Some('E')) | ('W', Some('R')) => i = 1,
+        ('X', _) => {
+            code.push('S');
+            i = 1;
+        }
+        ('W', Some('H')) => {
+            code.push('W');
+            i = 2;
+        }
+        _ => {}
+    }
+
+    while i < w.len() {
+        let c = w[i];
+        let prev = if i > 0 { Some(w[i - 1]) } else { None };
+        let next = at(i + 1);
+        let next2 = at(i + 2);
+
+        // Doubled letters are encoded once, except C
+        if prev == Some(c) && c != 'C' {
+            i += 1;
+            continue;
+        }
+
+        match c {
+            'A' | 'E' | 'I' | 'O' | 'U' => {
+                if i == 0 {
+                    code.push(c);
+                }
+            }
+            'B' => {
+                // Silent in a trailing "MB" (e.g. "lamb")
+                if !(prev == Some('M') && next.is_none()) {
+                    code.push('B');
+                }
+            }
+            'C' => {
+                if next == Some('H') {
+                    code.push(if prev == Some('S') { 'K' } else { 'X' });
+                } else if next == Some('I') && next2 == Some('A') {
+                    code.push('X');
+                } else if matches!(next, Some('I' | 'E' | 'Y')) {
+                    if prev != Some('S') {
+                        code.push('S');
+                    }
+                } else {
+                    code.push('K');
+                }
+            }
+            'D' => {
+                if next == Some('G') && matches!(next2, Some('E' | 'I' | 'Y')) {
+                    code.push('J');
+                    i += 1;
+                } else {
+                    code.push('T');
+                }
+            }
+            'G' => {
+                let silent_gh = next == Some('H') && next2.is_some() && !is_vowel(next2);
+                let silent_gn = next == Some('N')
+                    && (i + 2 == w.len()
+                        || (next2 == Some('E') && at(i + 3) == Some('D') && i + 4 == w.len()));
+                if silent_gh || silent_gn {
+                    // silent
+                } else if matches!(next, Some('I' | 'E' | 'Y')) && prev != Some('G') {
+                    code.push('J');
+                } else {
+                    code.push('K');
+                }
+            }
+            'H' => {
+                if is_vowel(next) && !matches!(prev, Some('C' | 'S' | 'P' | 'T' | 'G')) {
+                    code.push('H');
+                }
+            }
+            'K' => {
+                if prev != Some('C') {
+                    code.push('K');
+                }
+            }
+            'P' => code.push(if next == Some('H') { 'F' } else { 'P' }),
+            'Q' => code.push('K'),
+            'S' => {
+                if next == Some('H') || (next == Some('I') && matches!(next2, Some('O' | 'A'))) {
+                    code.push('X');
+                } else {
+                    code.push('S');
+                }
+            }
+            'T' => {
+                if next == Some('I') && matches!(next2, Some('O' | 'A')) {
+                    code.push('X');
+                } else if next == Some('H') {
+                    code.push('0');
+                } else if !(next == Some('C') && next2 == Some('H')) {
+                    code.push('T');
+                }
+            }
+            'V' => code.push('F'),
+            'W' | 'Y' => {
+                if is_vowel(next) {
+                    code.push(c);
+                }
+            }
+            'X' => code.push_str("KS"),
+            'Z' => code.push('S'),
+            _ => code.push(c),
+        }
+        i += 1;
+    }
+
+    code
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PhoneticCluster {
+    pub code: String,
+    pub words: Vec<String>,
+}
+
+/// Group words sharing the same phonetic code, in order of first appearance.
+fn cluster_by_code(words: &[String], algorithm: PhoneticAlgorithm) -> Vec<PhoneticCluster> {
+    let mut clusters: Vec<PhoneticCluster> = Vec::new();
+    for word in words {
+        let code = algorithm.encode(word);
+        match clusters.iter_mut().find(|c| c.code == code) {
+            Some(cluster) => cluster.words.push(word.clone()),
+            None => clusters.push(PhoneticCluster {
+                code,
+                words: vec![word.clone()],
+            }),
+        }
+    }
+    clusters
+}
+
+/// Group vocabulary words by their `"soundex"` or `"metaphone"` code
+#[wasm_bindgen]
+pub fn phonetic_cluster_vocabulary(words: JsValue, algorithm: &str) -> Result<JsValue, JsValue> {
+    let algorithm = PhoneticAlgorithm::parse(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown phonetic algorithm: {}", algorithm)))?;
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    let clusters = cluster_by_code(&words_vec, algorithm);
+
+    serde_wasm_bindgen::to_value(&clusters)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize clusters: {}", e)))
+}
+
+/// Whole-word transcriptions for short function words the rules get wrong
+const IPA_EXCEPTIONS: &[(&str, &[&str])] = &[
+    ("the", &["ð", "ə"]),
+    ("a", &["ə"]),
+    ("of", &["ə", "v"]),
+    ("to", &["t", "uː"]),
+    ("do", &["d", "uː"]),
+    ("you", &["j", "uː"]),
+    ("are", &["ɑː", "r"]),
+    ("was", &["w", "ɒ", "z"]),
+];
+
+/// Words where "th" is voiced (/ð/ rather than /θ/)
+const VOICED_TH_WORDS: &[&str] = &[
+    "this", "that", "these", "those", "then", "them", "there", "their", "they", "than", "though",
+    "thus", "with", "other", "mother", "father", "brother", "weather", "together",
+];
+
+fn is_vowel_letter(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+fn long_vowel(c: char) -> &'static str {
+    match c {
+        'a' => "eɪ",
+        'e' => "iː",
+        'i' => "aɪ",
+        'o' => "oʊ",
+        _ => "juː",
+    }
+}
+
+fn short_vowel(c: char) -> &'static str {
+    match c {
+        'a' => "æ",
+        'e' => "ɛ",
+        'i' => "ɪ",
+        'o' => "ɒ",
+        _ => "ʌ",
+    }
+}
+
+fn consonant(c: char) -> &'static str {
+    match c {
+        'b' => "b",
+        'd' => "d",
+        'f' => "f",
+        'h' => "h",
+        'j' => "dʒ",
+        'k' | 'q' => "k",
+        'l' => "l",
+        'm' => "m",
+        'n' => "n",
+        'p' => "p",
+        'r' => "r",
+        's' => "s",
+        't' => "t",
+        'v' => "v",
+        'w' => "w",
+        'z' => "z",
+        _ => "",
+    }
+}
+
+/// Approximate the English pronunciation of a word as a sequence of IPA
+/// phonemes using spelling rules (silent letters, digraphs, magic 'e',
+/// r-controlled vowels). Non-ASCII letters are ignored.
+pub(crate) fn english_phonemes(word: &str) -> Vec<&'static str> {
+    let lower = word.to_lowercase();
+    if let Some((_, phonemes)) = IPA_EXCEPTIONS.iter().find(|(w, _)| *w == lower) {
+        return phonemes.to_vec();
+    }
+
+    let w: Vec<char> = lower.chars().filter(|c| c.is_ascii_alphabetic()).collect();
+    let n = w.len();
+    let vowel_groups = w
+        .iter()
+        .enumerate()
+        .filter(|&(i, &c)| is_vowel_letter(c) && (i == 0 || !is_vowel_letter(w[i - 1])))
+        .count();
+
+    // Vowel-consonant-e at the end lengthens the vowel and the 'e' is silent
+    let magic_e = n >= 3
+        && w[n - 1] == 'e'
+        && !is_vowel_letter(w[n - 2])
+        && is_vowel_letter(w[n - 3])
+        && (n < 4 || !is_vowel_letter(w[n - 4]));
+    let voiced_th = VOICED_TH_WORDS.contains(&lower.as_str());
+
+    let mut out: Vec<&'static str> = Vec::new();
+    let mut i = 0;
+    while i < n {
+        let rest = &w[i..];
+        let starts = |pattern: &str| {
+            pattern.chars().count() <= rest.len() && pattern.chars().zip(rest).all(|(p, &c)| p == c)
+        };
+        let after = |len: usize| w.get(i + len).copied();
+        let r_controlled = |len: usize| {
+            after(len - 1) == Some('r')
+                && !after(len).is_some_and(|c| is_vowel_letter(c) || c == 'y')
+        };
+
+        // Silent initial letters: knight, gnome, write, psalm
+        if i == 0 && (starts("kn") || starts("gn") || starts("wr") || starts("ps")) {
+            i += 1;
+            continue;
+        }
+
+        let (phonemes, len): (&[&'static str], usize) = if starts("tch") {
+            (&["tʃ"], 3)
+        } else if starts("igh") {
+            (&["aɪ"], 3)
+        } else if starts("ch") {
+            (&["tʃ"], 2)
+        } else if starts("sh") {
+            (&["ʃ"], 2)
+        } else if starts("ph") {
+            (&["f"], 2)
+        } else if starts("th") {
+            (if voiced_th { &["ð"] } else { &["θ"] }, 2)
+        } else if starts("wh") {
+            (&["w"], 2)
+        } else if starts("ck") {
+            (&["k"], 2)
+        } else if starts("ng") {
+            (&["ŋ"], 2)
+        } else if starts("qu") {
+            (&["k", "w"], 2)
+        } else if starts("gh") {
+            (if i == 0 { &["g"] } else { &[] }, 2)
+        } else if starts("ee") || starts("ea") || starts("ie") {
+            (&["iː"], 2)
+        } else if starts("oo") {
+            (&["uː"], 2)
+        } else if starts("oa") {
+            (&["oʊ"], 2)
+        } else if starts("ai") || starts("ay") || starts("ei") {
+            (&["eɪ"], 2)
+        } else if starts("oi") || starts("oy") {
+            (&["ɔɪ"], 2)
+        } else if starts("au") || starts("aw") {
+            (&["ɔː"], 2)
+        } else if starts("ou") {
+            (&["aʊ"], 2)
+        } else if starts("ow") {
+            (if i + 2 == n { &["oʊ"] } else { &["aʊ"] }, 2)
+        } else if starts("ew") || starts("ue") {
+            (&["uː"], 2)
+        } else if starts("ar") && r_controlled(2) {
+            (&["ɑː", "r"], 2)
+        } else if starts("or") && r_controlled(2) {
+            (&["ɔː", "r"], 2)
+        } else if starts("er") && i + 2 == n && vowel_groups > 1 {
+            (&["ə", "r"], 2)
+        } else if (starts("er") || starts("ir") || starts("ur")) && r_controlled(2) {
+            (&["ɜː", "r"], 2)
+        } else {
+            let c = w[i];
+            let prev = if i > 0 { Some(w[i - 1]) } else { None };
+            let next = after(1);
+            let phoneme: &'static str = if is_vowel_letter(c) {
+                if magic_e && i == n - 3 {
+                    long_vowel(c)
+                } else if c == 'e' && i == n - 1 && vowel_groups > 1 {
+                    ""
+                } else if i == n - 1 && vowel_groups == 1 {
+                    // Open single-syllable words: he, go, hi
+                    long_vowel(c)
+                } else {
+                    short_vowel(c)
+                }
+            } else if prev == Some(c) {
+                // Doubled consonants are pronounced once
+                ""
+            } else {
+                match c {
+                    'c' | 'g' if next.is_some_and(|n| matches!(n, 'e' | 'i' | 'y')) => {
+                        if c == 'c' {
+                            "s"
+                        } else {
+                            "dʒ"
+                        }
+                    }
+                    'c' => "k",
+                    'g' => "g",
+                    'x' => {
+                        out.push("k");
+                        "s"
+                    }
+                    'y' if i == 0 || next.is_some_and(is_vowel_letter) => "j",
+                    'y' if i == n - 1 => {
+                        if vowel_groups == 0 {
+                            "aɪ"
+                        } else {
+                            "i"
+                        }
+                    }
+                    'y' => "ɪ",
+                    _ => consonant(c),
+                }
+            };
+            if !phoneme.is_empty() {
+                out.push(phoneme);
+            }
+            i += 1;
+            continue;
+        };
+
+        out.extend_from_slice(phonemes);
+        i += len;
+    }
+
+    out
+}
+
+/// Approximate IPA transcription of an English word (without delimiters),
+/// e.g. "phone" -> "foʊn"
+pub(crate) fn english_to_approximate_ipa(word: &str) -> String {
+    english_phonemes(word).concat()
+}
+
+/// Number of sounds in a word per the `english_phonemes` spelling rules,
+/// so digraphs count once and silent letters not at all ("ship" -> 3)
+fn approximate_phoneme_count(word: &str) -> usize {
+    english_phonemes(word).len()
+}
+
+/// Count the phonemes in an English word for phonemic awareness exercises
+#[wasm_bindgen]
+pub fn phoneme_count(word: &str) -> usize {
+    approximate_phoneme_count(word)
+}
+
+/// `similarity_score` of the approximate IPA transcriptions, so words that
+/// sound alike score high despite their spelling ("knight" and "night")
+fn pronunciation_similarity(s1: &str, s2: &str) -> f64 {
+    similarity_score(
+        &english_to_approximate_ipa(s1),
+        &english_to_approximate_ipa(s2),
+    )
+}
+
+/// Calculate how alike two English words sound
+#[wasm_bindgen]
+pub fn calculate_pronunciation_similarity(s1: &str, s2: &str) -> f64 {
+    pronunciation_similarity(s1, s2)
+}
+
+/// Pronunciation hint for a word in IPA brackets, e.g. "thin" -> "[θɪn]"
+#[wasm_bindgen]
+pub fn phonetic_hint(word: &str) -> String {
+    format!("[{}]", english_to_approximate_ipa(word))
+}
+
+/// Estimate the number of syllables in an English word by counting vowel
+/// groups, splitting "ia", dropping a silent final 'e' and adding one for
+/// consonant + "le" or a trailing "thm"/"sm" (e.g. "rhythm", "prism").
+/// Returns 0 for words without letters and at least 1 otherwise.
+#[wasm_bindgen]
+pub fn count_syllables(word: &str) -> usize {
+    let letters: String = word
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .collect();
+    if letters.is_empty() {
+        return 0;
+    }
+    let w: Vec<char> = letters.chars().collect();
+    let n = w.len();
+
+    // 'y' is a consonant at the start of a word and 'u' after 'q' ("queen")
+    let is_syllable_vowel = |i: usize| match w[i] {
+        'a' | 'e' | 'i' | 'o' => true,
+        'u' => i == 0 || w[i - 1] != 'q',
+        'y' => i > 0,
+        _ => false,
+    };
+
+    let mut count = 0;
+    for i in 0..n {
+        if !is_syllable_vowel(i) {
+            continue;
+        }
+        let starts_group = i == 0 || !is_syllable_vowel(i - 1);
+        // "ia" is usually two syllables (piano, median) except in -cial/-tial/-sia/-gia
+        let split_ia = i > 0
+            && w[i - 1] == 'i'
+            && w[i] == 'a'
+            && !(i >= 2 && matches!(w[i - 2], 'c' | 't' | 's' | 'g'));
+        if starts_group || split_ia {
+            count += 1;
+        }
+    }
+
+    if n >= 2 && w[n - 1] == 'e' && !is_syllable_vowel(n - 2) {
+        let consonant_le = n >= 3 && w[n - 2] == 'l' && !is_syllable_vowel(n - 3);
+        if !consonant_le {
+            count -= 1;
+        }
+    }
+    if (letters.ends_with("thm") || letters.ends_with("sm")) && n > 3 {
+        count += 1;
+    }
+
+    count.max(1)
+}
+
+/// Absolute difference between the syllable counts of two words
+#[wasm_bindgen]
+pub fn syllable_distance(s1: &str, s2: &str) -> usize {
+    count_syllables(s1).abs_diff(count_syllables(s2))
+}
+
+/// Whether a phoneme holds a vowel sound anywhere, so the glided long "u"
+/// ("juː") counts as well
+fn is_vowel_phoneme(phoneme: &str) -> bool {
+    phoneme.chars().any(|c| "aeiouæɛɪɒʌəɜɑɔʊ".contains(c))
+}
+
+/// Rhyme key of a word: its last vowel sound plus any trailing consonants,
+/// from the approximate IPA transcription (e.g. "cat" -> "æt").
+/// Words without a vowel sound use the whole transcription.
+pub(crate) fn rhyme_key(word: &str) -> String {
+    let phonemes = english_phonemes(word);
+    let start = phonemes
+        .iter()
+        .rposition(|p| is_vowel_phoneme(p))
+        .unwrap_or(0);
+    phonemes[start..].concat()
+}
+
+/// Vocabulary words sharing the rhyme key of `word`, excluding the word itself.
+fn rhymes_for(word: &str, vocabulary: &[String]) -> Vec<String> {
+    let key = rhyme_key(word);
+    if key.is_empty() {
+        return Vec::new();
+    }
+    vocabulary
+        .iter()
+        .filter(|candidate| !candidate.eq_ignore_ascii_case(word))
+        .filter(|candidate| rhyme_key(candidate) == key)
+        .cloned()
+        .collect()
+}
+
+/// Find all vocabulary words that rhyme with `word`
+#[wasm_bindgen]
+pub fn find_rhymes(word: &str, vocabulary: JsValue) -> Result<JsValue, JsValue> {
+    let vocab_vec: Vec<String> = serde_wasm_bindgen::from_value(vocabulary)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse vocabulary: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&rhymes_for(word, &vocab_vec))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize rhymes: {}", e)))
+}
+
+/// Consonant features: (IPA symbol, place of articulation, manner, voiced).
+/// Places run front to back (bilabial 0 .. glottal 7); manners are plosive 0,
+/// nasal 1, fricative 2, affricate 3, approximant 4, lateral 5.
+const IPA_CONSONANTS: &[(char, u8, u8, bool)] = &[
+    ('p', 0, 0, false),
+    ('b', 0, 0, true),
+    ('m', 0, 1, true),
+    ('w', 0, 4, true),
+    ('f', 1, 2, false),
+    ('v', 1, 2, true),
+    ('θ', 2, 2, false),
+    ('ð', 2, 2, true),
+    ('t', 3, 0, false),
+    ('d', 3, 0, true),
+    ('n', 3, 1, true),
+    ('s', 3, 2, false),
+    ('z', 3, 2, true),
+    ('r', 3, 4, true),
+    ('l', 3, 5, true),
+    ('ʃ', 4, 2, false),
+    ('ʒ', 4, 2, true),
+    ('j', 5, 4, true),
+    ('k', 6, 0, false),
+    ('g', 6, 0, true),
+    ('ŋ', 6, 1, true),
+    ('h', 7, 2, false),
+];
+
+/// Vowel features: (IPA symbol, height 0 (close) .. 3 (open), backness 0 (front) .. 2 (back), rounded)
+const IPA_VOWELS: &[(char, u8, u8, bool)] = &[
+    ('i', 0, 0, false),
+    ('ɪ', 0, 0, false),
+    ('e', 1, 0, false),
+    ('ɛ', 2, 0, false),
+    ('æ', 3, 0, false),
+    ('ə', 1, 1, false),
+    ('ɜ', 2, 1, false),
+    ('ʌ', 2, 1, false),
+    ('a', 3, 1, false),
+    ('u', 0, 2, true),
+    ('ʊ', 0, 2, true),
+    ('o', 1, 2, true),
+    ('ɔ', 2, 2, true),
+    ('ɒ', 3, 2, true),
+    ('ɑ', 3, 2, false),
+];
+
+/// Substitution costs between IPA symbols for phoneme-level edit distance.
+/// Pairs without an entry cost 1.0 (0.0 for identical symbols).
+pub struct PhonemeSubstitutionMatrix {
+    costs: HashMap<(char, char), f64>,
+}
+
+impl PhonemeSubstitutionMatrix {
+    /// Matrix where every substitution costs 1.0 (plain Levenshtein)
+    pub fn new() -> Self {
+        PhonemeSubstitutionMatrix {
+            costs: HashMap::new(),
+        }
+    }
+
+    /// English IPA preset where costs grow with articulatory distance:
+    /// voicing differences are cheap (/p/-/b/), manner changes dearer
+    /// (/p/-/m/) and consonant/vowel swaps cost the full 1.0.
+    pub fn english_ipa_default() -> Self {
+        let mut matrix = PhonemeSubstitutionMatrix::new();
+
+        for &(a, place_a, manner_a, voiced_a) in IPA_CONSONANTS {
+            for &(b, place_b, manner_b, voiced_b) in IPA_CONSONANTS {
+                if a == b {
+                    continue;
+                }
+                let voicing = if voiced_a == voiced_b { 0.0 } else { 0.2 };
+                let place = 0.1 * place_a.abs_diff(place_b) as f64;
+                let manner = if manner_a == manner_b { 0.0 } else { 0.4 };
+                matrix.set_cost(a, b, (voicing + place + manner).min(1.0));
+            }
+        }
+
+        for &(a, height_a, back_a, round_a) in IPA_VOWELS {
+            for &(b, height_b, back_b, round_b) in IPA_VOWELS {
+                if a == b {
+                    continue;
+                }
+                let height = 0.15 * height_a.abs_diff(height_b) as f64;
+                let backness = 0.15 * back_a.abs_diff(back_b) as f64;
+                let rounding = if round_a == round_b { 0.0 } else { 0.1 };
+                matrix.set_cost(a, b, (0.1 + height + backness + rounding).min(1.0));
+            }
+        }
+
+        matrix
+    }
+
+    /// Set the cost of substituting `a` for `b` (and `b` for `a`)
+    pub fn set_cost(&mut self, a: char, b: char, cost: f64) {
+        self.costs.insert((a, b), cost);
+        self.costs.insert((b, a), cost);
+    }
+
+    /// Cost of substituting `a` for `b`
+    pub fn cost(&self, a: char, b: char) -> f64 {
+        if a == b {
+            return 0.0;
+        }
+        self.costs.get(&(a, b)).copied().unwrap_or(1.0)
+    }
+}
+
+impl Default for PhonemeSubstitutionMatrix {
+    fn default() -> Self {
+        PhonemeSubstitutionMatrix::new()
+    }
+}
+
+/// Weighted Levenshtein distance between two IPA strings. Insertions and
+/// deletions cost 1.0, substitutions use `matrix`. Transcription delimiters
+/// (`/`, `[`, `]`) are ignored.
+pub(crate) fn phoneme_levenshtein(
+    ipa1: &str,
+    ipa2: &str,
+    matrix: &PhonemeSubstitutionMatrix,
+) -> f64 {
+    let strip = |s: &str| -> Vec<char> {
+        s.chars()
+            .filter(|c| !matches!(c, '/' | '[' | ']') && !c.is_whitespace())
+            .collect()
+    };
+    let a = strip(ipa1);
+    let b = strip(ipa2);
+
+    let mut prev: Vec<f64> = (0..=b.len()).map(|j| j as f64).collect();
+    for i in 1..=a.len() {
+        let mut row = vec![i as f64; b.len() + 1];
+        for j in 1..=b.len() {
+            row[j] = (prev[j] + 1.0) // deletion
+                .min(row[j - 1] + 1.0) // insertion
+                .min(prev[j - 1] + matrix.cost(a[i - 1], b[j - 1])); // substitution
+        }
+        prev = row;
+    }
+    prev[b.len()]
+}
+
+/// Articulation-weighted edit distance between two IPA transcriptions
+#[wasm_bindgen]
+pub fn calculate_phoneme_distance(ipa1: &str, ipa2: &str) -> f64 {
+    phoneme_levenshtein(
+        ipa1,
+        ipa2,
+        &PhonemeSubstitutionMatrix::english_ipa_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words;
+
+    #[test]
+    fn test_soundex() {
+        assert_eq!(soundex("Robert"), "R163");
+        assert_eq!(soundex("Rupert"), "R163");
+        assert_eq!(soundex("Ashcraft"), "A261");
+        assert_eq!(soundex("Tymczak"), "T522");
+        assert_eq!(soundex("cat"), "C300");
+        assert_eq!(soundex(""), "");
+    }
+
+    #[test]
+    fn test_metaphone() {
+        assert_eq!(metaphone("knight"), "NT");
+        assert_eq!(metaphone("night"), "NT");
+        assert_eq!(metaphone("phone"), "FN");
+        assert_eq!(metaphone("thumb"), "0M");
+        assert_eq!(metaphone("school"), "SKL");
+        assert_eq!(metaphone("judge"), "JJ");
+        assert_eq!(metaphone(""), "");
+    }
+
+    #[test]
+    fn test_cluster_by_code() {
+        let vocab = words(&["knight", "cat", "night", "bat"]);
+
+        let clusters = cluster_by_code(&vocab, PhoneticAlgorithm::Metaphone);
+        assert_eq!(clusters[0].words, vec!["knight", "night"]);
+
+        for algorithm in [PhoneticAlgorithm::Soundex, PhoneticAlgorithm::Metaphone] {
+            let clusters = cluster_by_code(&vocab, algorithm);
+            let cat = clusters
+                .iter()
+                .position(|c| c.words.contains(&"cat".to_string()));
+            let bat = clusters
+                .iter()
+                .position(|c| c.words.contains(&"bat".to_string()));
+            assert_ne!(cat, bat);
+        }
+    }
+
+    #[test]
+    fn test_parse_algorithm() {
+        assert_eq!(
+            PhoneticAlgorithm::parse("Soundex"),
+            Some(PhoneticAlgorithm::Soundex)
+        );
+        assert_eq!(
+            PhoneticAlgorithm::parse("metaphone"),
+            Some(PhoneticAlgorithm::Metaphone)
+        );
+        assert_eq!(PhoneticAlgorithm::parse("nysiis"), None);
+    }
+
+    #[test]
+    fn test_english_to_approximate_ipa() {
+        assert_eq!(english_to_approximate_ipa("phone"), "foʊn");
+        assert_eq!(english_to_approximate_ipa("thin"), "θɪn");
+        assert_eq!(english_to_approximate_ipa("the"), "ðə");
+        assert_eq!(english_to_approximate_ipa("knight"), "naɪt");
+        assert_eq!(english_to_approximate_ipa("night"), "naɪt");
+        assert_eq!(english_to_approximate_ipa("cat"), "kæt");
+        assert_eq!(english_to_approximate_ipa("ship"), "ʃɪp");
+        assert_eq!(english_to_approximate_ipa("cheese"), "tʃiːs");
+        assert_eq!(english_to_approximate_ipa(""), "");
+    }
+
+    #[test]
+    fn test_phonetic_hint() {
+        assert_eq!(phonetic_hint("phone"), "[foʊn]");
+        assert_eq!(phonetic_hint("Thin"), "[θɪn]");
+    }
+
+    #[test]
+    fn test_count_syllables() {
+        assert_eq!(count_syllables("beautiful"), 3);
+        assert_eq!(count_syllables("cat"), 1);
+        assert_eq!(count_syllables("table"), 2);
+        assert_eq!(count_syllables("rhythm"), 2);
+        assert_eq!(count_syllables("cake"), 1);
+        assert_eq!(count_syllables("queen"), 1);
+        assert_eq!(count_syllables("sesquipedalian"), 6);
+        assert_eq!(count_syllables(""), 0);
+    }
+
+    #[test]
+    fn test_syllable_distance() {
+        assert_eq!(syllable_distance("cat", "beautiful"), 2);
+        assert_eq!(syllable_distance("beautiful", "cat"), 2);
+        assert_eq!(syllable_distance("table", "cable"), 0);
+    }
+
+    #[test]
+    fn test_rhyme_key() {
+        assert_eq!(rhyme_key("cat"), "æt");
+        assert_eq!(rhyme_key("bat"), rhyme_key("sat"));
+        assert_ne!(rhyme_key("cat"), rhyme_key("dog"));
+        assert_eq!(rhyme_key("a"), "ə");
+        assert_eq!(rhyme_key("the"), "ə");
+        assert_eq!(rhyme_key(""), "");
+    }
+
+    #[test]
+    fn test_rhymes_for() {
+        let vocab = words(&["bat", "dog", "sat", "cat", "night"]);
+        assert_eq!(rhymes_for("cat", &vocab), vec!["bat", "sat"]);
+        assert_eq!(rhymes_for("knight", &vocab), vec!["night"]);
+        assert_eq!(
+            rhymes_for("cute", &words(&["mute", "flute", "cat"])),
+            vec!["mute", "flute"]
+        );
+        assert!(rhymes_for("the", &vocab).is_empty());
+        assert!(rhymes_for("", &vocab).is_empty());
+    }
+
+    #[test]
+    fn test_phoneme_substitution_matrix() {
+        let matrix = PhonemeSubstitutionMatrix::english_ipa_default();
+        assert!(matrix.cost('p', 'b') < matrix.cost('p', 'm'));
+        assert_eq!(matrix.cost('p', 'b'), matrix.cost('b', 'p'));
+        assert_eq!(matrix.cost('p', 'p'), 0.0);
+        assert_eq!(matrix.cost('p', 'æ'), 1.0);
+    }
+
+    #[test]
+    fn test_phoneme_levenshtein() {
+        let matrix = PhonemeSubstitutionMatrix::english_ipa_default();
+        assert_eq!(phoneme_levenshtein("/kæt/", "kæt", &matrix), 0.0);
+        assert!(
+            calculate_phoneme_distance("/p/", "/b/") < calculate_phoneme_distance("/p/", "/m/")
+        );
+        assert!(
+            calculate_phoneme_distance("pæt", "bæt") < calculate_phoneme_distance("pæt", "kæt")
+        );
+
+        let uniform = PhonemeSubstitutionMatrix::new();
+        assert_eq!(phoneme_levenshtein("pæt", "bæd", &uniform), 2.0);
+        assert_eq!(phoneme_levenshtein("", "kæt", &uniform), 3.0);
+    }
+
+    #[test]
+    fn test_phoneme_count() {
+        assert_eq!(phoneme_count("ship"), 3);
+        assert_eq!(phoneme_count("cat"), 3);
+        assert_eq!(phoneme_count("cheese"), 3);
+        assert_eq!(phoneme_count("the"), 2);
+        assert_eq!(approximate_phoneme_count("knight"), 3);
+        assert_eq!(approximate_phoneme_count(""), 0);
+    }
+
+    #[test]
+    fn test_pronunciation_similarity() {
+        let spoken = calculate_pronunciation_similarity("knight", "night");
+        assert_eq!(spoken, 1.0);
+        assert!(spoken > similarity_score("knight", "night"));
+        assert_eq!(pronunciation_similarity("phone", "fone"), 1.0);
+        assert!(pronunciation_similarity("cat", "dog") < 0.5);
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::VecDeque;
+
+use unicode_segmentation::UnicodeSegmentation;
+use wasm_bindgen::prelude::*;
+
+use crate::similarity_score;
+
+/// Lowercased word with its grapheme clusters in reverse order, so combining
+/// marks and emoji sequences stay attached to their base character.
+fn reversed_graphemes(word: &str) -> (String, String) {
+    let lower = word.to_lowercase();
+    let reversed = lower.graphemes(true).rev().collect();
+    (lower, reversed)
+}
+
+/// Whether a word reads the same backwards (case-insensitive, by grapheme)
+#[wasm_bindgen]
+pub fn is_palindrome(word: &str) -> bool {
+    let (lower, reversed) = reversed_graphemes(word);
+    lower == reversed
+}
+
+/// Degree of palindrome-ness: similarity of a word to its own reversal,
+/// 1.0 for true palindromes
+#[wasm_bindgen]
+pub fn palindrome_similarity(word: &str) -> f64 {
+    let (lower, reversed) = reversed_graphemes(word);
+    similarity_score(&lower, &reversed)
+}
+
+/// Vowel letters for `word_shape`, including common accented forms
+const SHAPE_VOWELS: &str = "aeiouàáâãäåèéêëìíîïòóôõöùúûü";
+
+/// Letter pattern of a word: 'V' for vowels, 'C' for other letters and 'N'
+/// for anything that isn't a letter ("cat" → "CVC", "it's" → "VCNC").
+pub(crate) fn word_shape(word: &str) -> String {
+    word.chars()
+        .map(|c| {
+            let lower = c.to_lowercase().next().unwrap_or(c);
+            if SHAPE_VOWELS.contains(lower) {
+                'V'
+            } else if c.is_alphabetic() {
+                'C'
+            } else {
+                'N'
+            }
+        })
+        .collect()
+}
+
+/// Vocabulary words with the same shape as `word`, in vocabulary order
+fn same_shape_words(word: &str, vocabulary: &[String]) -> Vec<String> {
+    let shape = word_shape(word);
+    vocabulary
+        .iter()
+        .filter(|candidate| word_shape(candidate) == shape)
+        .cloned()
+        .collect()
+}
+
+/// Find vocabulary words sharing the consonant/vowel pattern of `word`
+#[wasm_bindgen]
+pub fn find_same_shape_words(word: &str, vocabulary: JsValue) -> Result<JsValue, JsValue> {
+    let vocab_vec: Vec<String> = serde_wasm_bindgen::from_value(vocabulary)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse vocabulary: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&same_shape_words(word, &vocab_vec))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+/// `similarity_score` of the two word shapes ("cat" and "bat" are both "CVC")
+fn shape_similarity(s1: &str, s2: &str) -> f64 {
+    similarity_score(&word_shape(s1), &word_shape(s2))
+}
+
+/// Calculate how alike the consonant/vowel patterns of two words are
+#[wasm_bindgen]
+pub fn calculate_shape_similarity(s1: &str, s2: &str) -> f64 {
+    shape_similarity(s1, s2)
+}
+
+/// Vocabulary words whose shape similarity to `word` is at least
+/// `threshold`, in vocabulary order
+fn same_shape_words_fuzzy(word: &str, vocabulary: &[String], threshold: f64) -> Vec<String> {
+    let shape = word_shape(word);
+    vocabulary
+        .iter()
+        .filter(|candidate| similarity_score(&shape, &word_shape(candidate)) >= threshold)
+        .cloned()
+        .collect()
+}
+
+/// Find vocabulary words with a consonant/vowel pattern close to that of `word`
+#[wasm_bindgen]
+pub fn find_same_shape_words_fuzzy(
+    word: &str,
+    vocabulary: JsValue,
+    threshold: f64,
+) -> Result<JsValue, JsValue> {
+    let vocab_vec: Vec<String> = serde_wasm_bindgen::from_value(vocabulary)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse vocabulary: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&same_shape_words_fuzzy(word, &vocab_vec, threshold))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+/// Whether two words have the same length and differ in exactly one character
+fn one_substitution_apart(a: &[char], b: &[char]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).filter(|(x, y)| x != y).count() == 1
+}
+
+/// Shortest chain from `start` to `end` changing one character per step,
+/// where every word after `start` (including `end`) is in the vocabulary.
+/// Returns `None` when no chain exists.
+fn word_ladder(start: &str, end: &str, vocabulary: &[String]) -> Option<Vec<String>> {
+    if start == end {
+        return Some(vec![start.to_string()]);
+    }
+
+    let start_chars: Vec<char> = start.chars().collect();
+    let words: Vec<Vec<char>> = vocabulary.iter().map(|w| w.chars().collect()).collect();
+    // previous[i] is the index of the word before vocabulary[i] on its
+    // shortest path (`usize::MAX` when that is `start`), `None` if unvisited
+    let mut previous: Vec<Option<usize>> = vec![None; words.len()];
+    let mut queue = VecDeque::new();
+
+    for (i, word) in words.iter().enumerate() {
+        if one_substitution_apart(&start_chars, word) && previous[i].is_none() {
+            previous[i] = Some(usize::MAX);
+            queue.push_back(i);
+        }
+    }
+
+    while let Some(current) = queue.pop_front() {
+        if vocabulary[current] == end {
+            let mut path = vec![vocabulary[current].clone()];
+            let mut node = current;
+            while let Some(prev) = previous[node].filter(|&p| p != usize::MAX) {
+                path.push(vocabulary[prev].clone());
+                node = prev;
+            }
+            path.push(start.to_string());
+            path.reverse();
+            return Some(path);
+        }
+
+        for (i, word) in words.iter().enumerate() {
+            if previous[i].is_none() && one_substitution_apart(&words[current], word) {
+                previous[i] = Some(current);
+                queue.push_back(i);
+            }
+        }
+    }
+
+    None
+}
+
+/// Find the shortest word ladder from `start` to `end` through the
+/// vocabulary as an array of words, or `null` if there is none
+#[wasm_bindgen(js_name = word_ladder)]
+pub fn find_word_ladder(start: &str, end: &str, vocabulary: JsValue) -> Result<JsValue, JsValue> {
+    let vocab_vec: Vec<String> = serde_wasm_bindgen::from_value(vocabulary)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse vocabulary: {}", e)))?;
+
+    match word_ladder(start, end, &vocab_vec) {
+        Some(path) => serde_wasm_bindgen::to_value(&path)
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e))),
+        None => Ok(JsValue::NULL),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words;
+
+    #[test]
+    fn test_is_palindrome() {
+        assert!(is_palindrome("racecar"));
+        assert!(is_palindrome("Level"));
+        assert!(is_palindrome(""));
+        assert!(!is_palindrome("race"));
+        // "e" + combining acute stays one grapheme
+        assert!(is_palindrome("e\u{301}te\u{301}"));
+    }
+
+    #[test]
+    fn test_palindrome_similarity() {
+        assert_eq!(palindrome_similarity("level"), 1.0);
+        assert!(palindrome_similarity("hello") < palindrome_similarity("level"));
+        assert!(palindrome_similarity("hello") >= 0.0);
+    }
+
+    #[test]
+    fn test_word_shape() {
+        assert_eq!(word_shape("cat"), "CVC");
+        assert_eq!(word_shape("bat"), word_shape("cat"));
+        assert_ne!(word_shape("cape"), word_shape("cat"));
+        assert_eq!(word_shape("it's"), "VCNC");
+        assert_eq!(word_shape("Über"), "VCVC");
+        assert_eq!(word_shape(""), "");
+    }
+
+    #[test]
+    fn test_same_shape_words() {
+        let vocab = words(&["them", "then", "than", "that", "cat", "they"]);
+        assert_eq!(
+            same_shape_words("them", &vocab),
+            vec!["them", "then", "than", "that", "they"]
+        );
+        assert!(same_shape_words("", &vocab).is_empty());
+    }
+
+    #[test]
+    fn test_word_ladder() {
+        let vocab = words(&["cot", "dot", "dog", "cog", "bat", "bad", "bed", "cat"]);
+        let path = word_ladder("cat", "dog", &vocab).unwrap();
+        assert_eq!(path.len(), 4);
+        assert_eq!(path.first().map(String::as_str), Some("cat"));
+        assert_eq!(path.last().map(String::as_str), Some("dog"));
+        for step in path.windows(2) {
+            let a: Vec<char> = step[0].chars().collect();
+            let b: Vec<char> = step[1].chars().collect();
+            assert!(one_substitution_apart(&a, &b), "{:?}", step);
+        }
+
+        assert_eq!(
+            word_ladder("cat", "bed", &vocab).unwrap(),
+            vec!["cat", "bat", "bad", "bed"]
+        );
+    }
+
+    #[test]
+    fn test_word_ladder_edge_cases() {
+        assert_eq!(word_ladder("cat", "cat", &[]).unwrap(), vec!["cat"]);
+        assert!(word_ladder("cat", "dog", &words(&["cot", "dog"])).is_none());
+        assert!(word_ladder("cat", "cart", &words(&["cart"])).is_none());
+    }
+
+    #[test]
+    fn test_shape_similarity() {
+        assert_eq!(calculate_shape_similarity("cat", "bat"), 1.0);
+        let plural = calculate_shape_similarity("cat", "cats");
+        assert!(plural < 1.0 && plural > 0.5);
+        assert_eq!(shape_similarity("", ""), 1.0);
+        assert!((shape_similarity("42!", "7?") - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(shape_similarity("cat", "123"), 0.0);
+    }
+
+    #[test]
+    fn test_same_shape_words_fuzzy() {
+        let vocab = words(&["bat", "cats", "tree", "dog", "a"]);
+        assert_eq!(
+            same_shape_words_fuzzy("cat", &vocab, 0.7),
+            vec!["bat", "cats", "dog"]
+        );
+        assert_eq!(
+            same_shape_words_fuzzy("cat", &vocab, 1.0),
+            vec!["bat", "dog"]
+        );
+    }
+}
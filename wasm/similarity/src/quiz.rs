@@ -0,0 +1,534 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::alignment::{edit_steps, EditStep};
+use crate::{similarity_score, top_n_matches, MatchResult};
+
+/// One quiz question: the expected word and what the learner typed
+#[derive(Serialize, Deserialize)]
+pub struct QuizPair {
+    pub correct: String,
+    pub user_answer: String,
+}
+
+/// Grade every answer against its own expected word, preserving input order.
+/// Each result is the best match of the answer against a one-word list.
+fn grade_pairs(pairs: &[QuizPair]) -> Vec<MatchResult> {
+    pairs
+        .iter()
+        .map(|pair| MatchResult::from_pair(&pair.user_answer, &pair.correct))
+        .collect()
+}
+
+/// Grade a whole quiz of `{ correct, user_answer }` pairs at once, returning
+/// one match result per pair in the same order
+#[wasm_bindgen]
+pub fn batch_find_best_match(pairs: JsValue) -> Result<JsValue, JsValue> {
+    let pairs_vec: Vec<QuizPair> = serde_wasm_bindgen::from_value(pairs)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse pairs: {}", e)))?;
+
+    let results = grade_pairs(&pairs_vec);
+
+    serde_wasm_bindgen::to_value(&results)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuizFeedback {
+    /// Fraction of answers that passed, in [0, 1]
+    pub score: f64,
+    /// Expected words answered well enough, in quiz order
+    pub passed: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+fn check_passing_similarity(passing_similarity: f64) -> Result<(), String> {
+    if (0.0..=1.0).contains(&passing_similarity) {
+        Ok(())
+    } else {
+        Err(format!(
+            "passing_similarity must be between 0.0 and 1.0, got {}",
+            passing_similarity
+        ))
+    }
+}
+
+/// Split graded answers into passed and failed words. An empty quiz scores 0.0.
+fn quiz_result(results: &[MatchResult], passing_similarity: f64) -> Result<QuizFeedback, String> {
+    check_passing_similarity(passing_similarity)?;
+
+    let (passed, failed): (Vec<&MatchResult>, Vec<&MatchResult>) = results
+        .iter()
+        .partition(|m| m.similarity >= passing_similarity);
+    let score = if results.is_empty() {
+        0.0
+    } else {
+        passed.len() as f64 / results.len() as f64
+    };
+
+    Ok(QuizFeedback {
+        score,
+        passed: passed.into_iter().map(|m| m.word.clone()).collect(),
+        failed: failed.into_iter().map(|m| m.word.clone()).collect(),
+    })
+}
+
+/// Fraction of graded answers with similarity of at least `passing_similarity`
+#[wasm_bindgen]
+pub fn quiz_score(match_results: JsValue, passing_similarity: f64) -> Result<f64, JsValue> {
+    let results: Vec<MatchResult> = serde_wasm_bindgen::from_value(match_results)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse match results: {}", e)))?;
+
+    quiz_result(&results, passing_similarity)
+        .map(|feedback| feedback.score)
+        .map_err(|e| JsValue::from_str(&e))
+}
+
+/// Score graded answers and list the passed and failed words as
+/// `{ score, passed, failed }`
+#[wasm_bindgen]
+pub fn quiz_feedback(match_results: JsValue, passing_similarity: f64) -> Result<JsValue, JsValue> {
+    let results: Vec<MatchResult> = serde_wasm_bindgen::from_value(match_results)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse match results: {}", e)))?;
+
+    let feedback = quiz_result(&results, passing_similarity).map_err(|e| JsValue::from_str(&e))?;
+
+    serde_wasm_bindgen::to_value(&feedback)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+/// Pass rate the adaptive threshold steers towards
+const TARGET_PASS_RATE: f64 = 0.75;
+/// How far the threshold moves per answer for each unit of pass-rate error
+const THRESHOLD_STEP: f64 = 0.05;
+const MIN_THRESHOLD: f64 = 0.5;
+const MAX_THRESHOLD: f64 = 0.95;
+const INITIAL_THRESHOLD: f64 = 0.8;
+
+/// Passing threshold that adapts to the learner's recent answers, raising the
+/// bar while they pass more than ~75% of questions and lowering it otherwise
+#[wasm_bindgen]
+pub struct WasmAdaptiveDifficulty {
+    window_size: usize,
+    scores: VecDeque<f64>,
+    threshold: f64,
+}
+
+#[wasm_bindgen]
+impl WasmAdaptiveDifficulty {
+    /// Track the last `window_size` answers (at least one)
+    #[wasm_bindgen(constructor)]
+    pub fn new(window_size: usize) -> WasmAdaptiveDifficulty {
+        let window_size = window_size.max(1);
+        WasmAdaptiveDifficulty {
+            window_size,
+            scores: VecDeque::with_capacity(window_size),
+            threshold: INITIAL_THRESHOLD,
+        }
+    }
+
+    /// Record the similarity of an answer and nudge the threshold towards
+    /// the target pass rate
+    pub fn record_score(&mut self, similarity: f64) {
+        if self.scores.len() == self.window_size {
+            self.scores.pop_front();
+        }
+        self.scores.push_back(similarity.clamp(0.0, 1.0));
+
+        let error = self.current_pass_rate() - TARGET_PASS_RATE;
+        self.threshold =
+            (self.threshold + THRESHOLD_STEP * error).clamp(MIN_THRESHOLD, MAX_THRESHOLD);
+    }
+
+    /// Similarity an answer currently needs in order to pass
+    pub fn recommended_threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Fraction of answers in the window passing the current threshold,
+    /// or 0.0 before any answer is recorded
+    pub fn current_pass_rate(&self) -> f64 {
+        if self.scores.is_empty() {
+            return 0.0;
+        }
+        let passed = self.scores.iter().filter(|&&s| s >= self.threshold).count();
+        passed as f64 / self.scores.len() as f64
+    }
+}
+
+/// Lowest similarity a near-miss distractor may have to the correct answer
+const DISTRACTOR_MIN_SIMILARITY: f64 = 0.6;
+/// Highest similarity, so distractors stay distinguishable from the answer
+const DISTRACTOR_MAX_SIMILARITY: f64 = 0.95;
+
+/// Up to `n` vocabulary words that look almost, but not quite, like `correct`
+fn near_miss_distractors(correct: &str, n: usize, vocabulary: &[String]) -> Vec<String> {
+    top_n_matches(
+        correct,
+        vocabulary,
+        n,
+        DISTRACTOR_MIN_SIMILARITY,
+        DISTRACTOR_MAX_SIMILARITY,
+    )
+    .into_iter()
+    .map(|m| m.word)
+    .collect()
+}
+
+/// Pick `n` multiple-choice distractors that are close to the correct answer;
+/// returns fewer when the vocabulary doesn't have enough near misses
+#[wasm_bindgen]
+pub fn generate_near_miss_distractors(
+    correct: &str,
+    n: usize,
+    vocabulary: JsValue,
+) -> Result<JsValue, JsValue> {
+    let vocab_vec: Vec<String> = serde_wasm_bindgen::from_value(vocabulary)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse vocabulary: {}", e)))?;
+
+    let distractors = near_miss_distractors(correct, n, &vocab_vec);
+
+    serde_wasm_bindgen::to_value(&distractors)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+/// Ways of presenting a similarity score to a learner
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GradeScale {
+    Letter,
+    Percent,
+    Stars,
+}
+
+impl GradeScale {
+    /// Parse a scale name as passed from JS (`"letter"`, `"percent"` or `"stars"`)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "letter" => Some(GradeScale::Letter),
+            "percent" => Some(GradeScale::Percent),
+            "stars" => Some(GradeScale::Stars),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Grade {
+    /// "A".."F", "0".."100" or "1 star".."5 stars" depending on the scale
+    pub grade: String,
+    pub label: String,
+}
+
+/// Lower similarity bound, letter and label of each band, best first
+const GRADE_BANDS: &[(f64, &str, &str)] = &[
+    (0.9, "A", "Excellent"),
+    (0.8, "B", "Good"),
+    (0.7, "C", "Fair"),
+    (0.6, "D", "Needs work"),
+    (0.0, "F", "Try again"),
+];
+
+/// Express a similarity in [0, 1] on a learner-friendly scale. Stars are
+/// `floor(similarity * 5)` clamped to 1..=5; every scale shares the labels
+/// of the letter bands.
+fn grade_for(similarity: f64, scale: GradeScale) -> Result<Grade, String> {
+    if !(0.0..=1.0).contains(&similarity) {
+        return Err(format!(
+            "similarity must be between 0.0 and 1.0, got {}",
+            similarity
+        ));
+    }
+
+    let (_, letter, label) = GRADE_BANDS
+        .iter()
+        .find(|(min, _, _)| similarity >= *min)
+        .copied()
+        .unwrap_or(GRADE_BANDS[GRADE_BANDS.len() - 1]);
+    let grade = match scale {
+        GradeScale::Letter => letter.to_string(),
+        GradeScale::Percent => format!("{}", (similarity * 100.0).round()),
+        GradeScale::Stars => match ((similarity * 5.0).floor() as u8).clamp(1, 5) {
+            1 => "1 star".to_string(),
+            stars => format!("{} stars", stars),
+        },
+    };
+
+    Ok(Grade {
+        grade,
+        label: label.to_string(),
+    })
+}
+
+/// Turn a similarity into `{ grade, label }` on the `"letter"`, `"percent"`
+/// or `"stars"` scale
+#[wasm_bindgen]
+pub fn similarity_to_grade(similarity: f64, scale: &str) -> Result<JsValue, JsValue> {
+    let scale = GradeScale::parse(scale)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown grade scale: {}", scale)))?;
+
+    let grade = grade_for(similarity, scale).map_err(|e| JsValue::from_str(&e))?;
+
+    serde_wasm_bindgen::to_value(&grade)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DetailedGrade {
+    pub similarity: f64,
+    /// Letter grade, see `similarity_to_grade`
+    pub grade: String,
+    pub num_errors: usize,
+    /// Kinds of mistakes in order of appearance, each listed once:
+    /// "substitution", "insertion" (extra letter), "deletion" (missing
+    /// letter) or "transposition" (two neighbours swapped)
+    pub error_types: Vec<String>,
+    /// The correct answer to show the learner
+    pub suggestion: String,
+}
+
+/// Grade an answer and break its mistakes down by kind. A swapped pair of
+/// neighbouring letters counts as one transposition instead of two
+/// substitutions.
+fn detailed_grade(user_answer: &str, correct_answer: &str) -> DetailedGrade {
+    let steps = edit_steps(user_answer, correct_answer);
+    let mut num_errors = 0;
+    let mut error_types: Vec<String> = Vec::new();
+
+    let mut i = 0;
+    while i < steps.len() {
+        let kind = match (steps[i], steps.get(i + 1)) {
+            (EditStep::Match(_), _) => None,
+            (EditStep::Substitute(a, b), Some(&EditStep::Substitute(c, d))) if a == d && b == c => {
+                i += 1;
+                Some("transposition")
+            }
+            (EditStep::Substitute(..), _) => Some("substitution"),
+            (EditStep::Delete(_), _) => Some("insertion"),
+            (EditStep::Insert(_), _) => Some("deletion"),
+        };
+        if let Some(kind) = kind {
+            num_errors += 1;
+            if !error_types.iter().any(|t| t == kind) {
+                error_types.push(kind.to_string());
+            }
+        }
+        i += 1;
+    }
+
+    let similarity = similarity_score(user_answer, correct_answer);
+    let grade = grade_for(similarity, GradeScale::Letter)
+        .map(|g| g.grade)
+        .unwrap_or_default();
+
+    DetailedGrade {
+        similarity,
+        grade,
+        num_errors,
+        error_types,
+        suggestion: correct_answer.to_string(),
+    }
+}
+
+/// Grade an answer as `{ similarity, grade, num_errors, error_types,
+/// suggestion }` so the UI can explain what went wrong
+#[wasm_bindgen]
+pub fn grade_answer_detailed(user_answer: &str, correct_answer: &str) -> JsValue {
+    serde_wasm_bindgen::to_value(&detailed_grade(user_answer, correct_answer))
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words;
+
+    fn pairs(list: &[(&str, &str)]) -> Vec<QuizPair> {
+        list.iter()
+            .map(|(correct, answer)| QuizPair {
+                correct: correct.to_string(),
+                user_answer: answer.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_grade_pairs_preserves_order() {
+        let quiz = pairs(&[("house", "hous"), ("cat", "cat"), ("receive", "recieve")]);
+        let results = grade_pairs(&quiz);
+
+        assert_eq!(results.len(), quiz.len());
+        let graded: Vec<&str> = results.iter().map(|m| m.word.as_str()).collect();
+        assert_eq!(graded, vec!["house", "cat", "receive"]);
+        assert!(!results[0].is_exact);
+        assert!(results[1].is_exact);
+        assert_eq!(results[2].distance, 2);
+    }
+
+    #[test]
+    fn test_grade_pairs_all_correct() {
+        let quiz = pairs(&[("one", "one"), ("two", "two"), ("three", "three")]);
+        let results = grade_pairs(&quiz);
+        assert!(results.iter().all(|m| m.is_exact && m.similarity == 1.0));
+    }
+
+    #[test]
+    fn test_grade_pairs_empty() {
+        assert!(grade_pairs(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_quiz_result_half_passing() {
+        let quiz = pairs(&[
+            ("one", "one"),
+            ("two", "two"),
+            ("three", "three"),
+            ("four", "four"),
+            ("five", "five"),
+            ("six", "xyz"),
+            ("seven", "abc"),
+            ("eight", "qqq"),
+            ("nine", "zzz"),
+            ("ten", "uuu"),
+        ]);
+        let feedback = quiz_result(&grade_pairs(&quiz), 0.8).unwrap();
+        assert_eq!(feedback.score, 0.5);
+        assert_eq!(feedback.passed, vec!["one", "two", "three", "four", "five"]);
+        assert_eq!(
+            feedback.failed,
+            vec!["six", "seven", "eight", "nine", "ten"]
+        );
+    }
+
+    #[test]
+    fn test_quiz_result_all_passing() {
+        let quiz = pairs(&[("house", "house"), ("garden", "gardn")]);
+        let feedback = quiz_result(&grade_pairs(&quiz), 0.8).unwrap();
+        assert_eq!(feedback.score, 1.0);
+        assert!(feedback.failed.is_empty());
+    }
+
+    #[test]
+    fn test_quiz_result_invalid_threshold() {
+        let results = grade_pairs(&pairs(&[("cat", "cat")]));
+        assert!(quiz_result(&results, 1.5).is_err());
+        assert!(quiz_result(&results, -0.1).is_err());
+        assert!(quiz_result(&results, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_quiz_result_empty() {
+        assert_eq!(quiz_result(&[], 0.8).unwrap().score, 0.0);
+    }
+
+    #[test]
+    fn test_adaptive_difficulty_raises_threshold() {
+        let mut adaptive = WasmAdaptiveDifficulty::new(10);
+        let start = adaptive.recommended_threshold();
+        let mut previous = start;
+        for _ in 0..20 {
+            adaptive.record_score(1.0);
+            assert!(adaptive.recommended_threshold() >= previous);
+            previous = adaptive.recommended_threshold();
+        }
+        assert!(adaptive.recommended_threshold() > start);
+        assert!(adaptive.recommended_threshold() <= MAX_THRESHOLD);
+        assert_eq!(adaptive.current_pass_rate(), 1.0);
+    }
+
+    #[test]
+    fn test_adaptive_difficulty_lowers_threshold() {
+        let mut adaptive = WasmAdaptiveDifficulty::new(10);
+        let start = adaptive.recommended_threshold();
+        for _ in 0..100 {
+            adaptive.record_score(0.0);
+        }
+        assert!(adaptive.recommended_threshold() < start);
+        assert_eq!(adaptive.recommended_threshold(), MIN_THRESHOLD);
+        assert_eq!(adaptive.current_pass_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_adaptive_difficulty_window() {
+        let mut adaptive = WasmAdaptiveDifficulty::new(2);
+        assert_eq!(adaptive.current_pass_rate(), 0.0);
+        adaptive.record_score(0.0);
+        adaptive.record_score(1.0);
+        adaptive.record_score(1.0);
+        // The first failure has left the window
+        assert_eq!(adaptive.current_pass_rate(), 1.0);
+    }
+
+    #[test]
+    fn test_near_miss_distractors() {
+        let vocab = words(&[
+            "receive", "recieve", "receiver", "deceive", "table", "receipt",
+        ]);
+        let distractors = near_miss_distractors("receive", 3, &vocab);
+
+        assert_eq!(distractors.len(), 3);
+        assert!(!distractors.contains(&"receive".to_string()));
+        assert!(!distractors.contains(&"table".to_string()));
+        assert!(distractors.iter().all(|d| vocab.contains(d)));
+    }
+
+    #[test]
+    fn test_near_miss_distractors_limits() {
+        let vocab = words(&["receive", "deceive", "table"]);
+        assert!(near_miss_distractors("receive", 0, &vocab).is_empty());
+        assert_eq!(near_miss_distractors("receive", 5, &vocab), vec!["deceive"]);
+    }
+
+    #[test]
+    fn test_grade_for() {
+        let grade = |s, scale| grade_for(s, scale).unwrap().grade;
+        assert_eq!(grade(1.0, GradeScale::Letter), "A");
+        assert_eq!(grade(1.0, GradeScale::Percent), "100");
+        assert_eq!(grade(1.0, GradeScale::Stars), "5 stars");
+        assert_eq!(grade(0.5, GradeScale::Letter), "F");
+        assert_eq!(grade(0.5, GradeScale::Percent), "50");
+        assert_eq!(grade(0.5, GradeScale::Stars), "2 stars");
+        assert_eq!(grade(0.0, GradeScale::Stars), "1 star");
+        assert_eq!(grade(0.85, GradeScale::Letter), "B");
+
+        assert_eq!(
+            grade_for(1.0, GradeScale::Stars).unwrap().label,
+            "Excellent"
+        );
+        assert!(grade_for(1.2, GradeScale::Letter).is_err());
+    }
+
+    #[test]
+    fn test_parse_grade_scale() {
+        assert_eq!(GradeScale::parse("Stars"), Some(GradeScale::Stars));
+        assert_eq!(GradeScale::parse("gpa"), None);
+    }
+
+    #[test]
+    fn test_detailed_grade_correct() {
+        let result = detailed_grade("house", "house");
+        assert_eq!(result.grade, "A");
+        assert_eq!(result.num_errors, 0);
+        assert!(result.error_types.is_empty());
+        assert_eq!(result.similarity, 1.0);
+        assert_eq!(result.suggestion, "house");
+    }
+
+    #[test]
+    fn test_detailed_grade_error_types() {
+        let swapped = detailed_grade("hosue", "house");
+        assert_eq!(swapped.num_errors, 1);
+        assert_eq!(swapped.error_types, vec!["transposition"]);
+
+        let extra = detailed_grade("houses", "house");
+        assert_eq!(extra.error_types, vec!["insertion"]);
+
+        let missing = detailed_grade("hose", "house");
+        assert_eq!(missing.error_types, vec!["deletion"]);
+
+        let wrong = detailed_grade("mouse", "house");
+        assert_eq!(wrong.error_types, vec!["substitution"]);
+        assert_eq!(wrong.grade, "B");
+    }
+}
@@ -0,0 +1,542 @@
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::MatchResult;
+
+/// SM-2 review state of one flashcard as stored by the app
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CardState {
+    pub word: String,
+    /// SM-2 ease factor, 2.5 for a new card and never below 1.3
+    pub ease_factor: f64,
+    /// Days until the next review
+    pub interval: u32,
+    /// Consecutive successful reviews
+    pub repetitions: u32,
+    /// Unix time (seconds) at which the card is due
+    pub next_review_unix: u64,
+    /// Mastery in [0, 1]
+    pub mastery: f64,
+}
+
+/// Up to `n` words due by `today_unix`, most forgotten (lowest mastery)
+/// first; equal mastery puts the longer overdue card first. Vocabulary words
+/// without a card state have never been studied, so they are due with
+/// mastery 0.0. Card states for words outside the vocabulary are ignored.
+fn study_plan(
+    vocabulary: &[String],
+    card_states: &[CardState],
+    n: usize,
+    today_unix: u64,
+) -> Vec<String> {
+    let states: HashMap<&str, &CardState> =
+        card_states.iter().map(|c| (c.word.as_str(), c)).collect();
+
+    let mut due: Vec<(f64, u64, &String)> = vocabulary
+        .iter()
+        .filter_map(|word| match states.get(word.as_str()) {
+            Some(card) if card.next_review_unix <= today_unix => {
+                Some((card.mastery, card.next_review_unix, word))
+            }
+            Some(_) => None,
+            None => Some((0.0, 0, word)),
+        })
+        .collect();
+
+    due.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    due.into_iter()
+        .take(n)
+        .map(|(_, _, word)| word.clone())
+        .collect()
+}
+
+/// Recommend up to `n` words to study today from an array of words and
+/// their `CardState`s: only due cards, most forgotten first
+#[wasm_bindgen]
+pub fn daily_study_plan(
+    vocabulary: JsValue,
+    card_states: JsValue,
+    n: usize,
+    today_unix: u64,
+) -> Result<JsValue, JsValue> {
+    let vocab_vec: Vec<String> = serde_wasm_bindgen::from_value(vocabulary)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse vocabulary: {}", e)))?;
+    let states: Vec<CardState> = serde_wasm_bindgen::from_value(card_states)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse card states: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&study_plan(&vocab_vec, &states, n, today_unix))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+/// Seconds in a day, for turning SM-2 intervals into review times
+const SECONDS_PER_DAY: u64 = 86_400;
+/// Lowest ease factor SM-2 allows
+const MIN_EASE_FACTOR: f64 = 1.3;
+/// Mastery at which a word counts as learned
+const TARGET_MASTERY: f64 = 0.9;
+/// Quality assumed for every simulated review ("correct after hesitation")
+const SIMULATED_QUALITY: u8 = 4;
+/// Safety limit for the simulation
+const MAX_SIMULATED_SESSIONS: u32 = 100;
+
+/// Apply one SM-2 review of `quality` (0-5, clamped) taken at `review_unix`.
+/// Answers below 3 restart the repetition count; the ease factor is adjusted
+/// by `0.1 - (5 - q) * (0.08 + (5 - q) * 0.02)` and kept above 1.3.
+pub(crate) fn sm2_review(card: &CardState, quality: u8, review_unix: u64) -> CardState {
+    let quality = quality.min(5);
+    let mut next = card.clone();
+
+    if quality >= 3 {
+        next.interval = match card.repetitions {
+            0 => 1,
+            1 => 6,
+            _ => (card.interval as f64 * card.ease_factor).round() as u32,
+        };
+        next.repetitions = card.repetitions + 1;
+    } else {
+        next.repetitions = 0;
+        next.interval = 1;
+    }
+
+    let miss = (5 - quality) as f64;
+    next.ease_factor = (card.ease_factor + 0.1 - miss * (0.08 + miss * 0.02)).max(MIN_EASE_FACTOR);
+    next.next_review_unix = review_unix.saturating_add(next.interval as u64 * SECONDS_PER_DAY);
+    next
+}
+
+/// Mastery after a successful review that left the card at `card`: each one
+/// closes `(ease - 1.3) / ease` of the remaining gap to full mastery, so
+/// easier cards are learned faster.
+fn mastery_after_review(mastery: f64, card: &CardState) -> f64 {
+    let gain = (card.ease_factor - MIN_EASE_FACTOR) / card.ease_factor;
+    // A card stuck at the minimum ease still makes some progress
+    mastery + (1.0 - mastery) * gain.max(0.1)
+}
+
+/// Number of reviews (each answered with quality 4 when due) until mastery
+/// reaches 0.9, see `mastery_after_review`.
+fn learning_sessions(current_mastery: f64, card: &CardState) -> u32 {
+    let mut mastery = current_mastery.clamp(0.0, 1.0);
+    let mut card = card.clone();
+    let mut sessions = 0;
+
+    while mastery < TARGET_MASTERY && sessions < MAX_SIMULATED_SESSIONS {
+        card = sm2_review(&card, SIMULATED_QUALITY, card.next_review_unix);
+        mastery = mastery_after_review(mastery, &card);
+        sessions += 1;
+    }
+    sessions
+}
+
+/// Estimate how many more review sessions a word needs to reach mastery 0.9
+/// from its current mastery and `CardState`
+#[wasm_bindgen]
+pub fn estimate_learning_time(current_mastery: f64, card_state: JsValue) -> Result<f64, JsValue> {
+    let card: CardState = serde_wasm_bindgen::from_value(card_state)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse card state: {}", e)))?;
+
+    Ok(learning_sessions(current_mastery, &card) as f64)
+}
+
+/// Ease factor SM-2 gives a card that has never been reviewed
+const INITIAL_EASE_FACTOR: f64 = 2.5;
+/// Weight of the latest answer in a card's running mastery
+const MASTERY_SMOOTHING: f64 = 0.5;
+
+impl CardState {
+    /// State of a word that has never been reviewed
+    pub fn new(word: &str, now_unix: u64) -> Self {
+        CardState {
+            word: word.to_string(),
+            ease_factor: INITIAL_EASE_FACTOR,
+            interval: 0,
+            repetitions: 0,
+            next_review_unix: now_unix,
+            mastery: 0.0,
+        }
+    }
+}
+
+/// One quiz session mixing new words with reviews. New words (those without
+/// a card state) alternate with review words, most overdue review first;
+/// whatever is left of either list follows at the end.
+#[wasm_bindgen]
+pub struct WasmQuizSession {
+    queue: VecDeque<String>,
+    cards: Vec<CardState>,
+    current: Option<String>,
+}
+
+#[wasm_bindgen]
+impl WasmQuizSession {
+    /// Start a session over an array of words and their `CardState`s,
+    /// introducing at most `new_per_session` new words
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        vocabulary: JsValue,
+        card_states: JsValue,
+        new_per_session: usize,
+    ) -> Result<WasmQuizSession, JsValue> {
+        let vocab_vec: Vec<String> = serde_wasm_bindgen::from_value(vocabulary)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse vocabulary: {}", e)))?;
+        let states: Vec<CardState> = serde_wasm_bindgen::from_value(card_states)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse card states: {}", e)))?;
+
+        Ok(WasmQuizSession::from_cards(
+            &vocab_vec,
+            states,
+            new_per_session,
+        ))
+    }
+
+    /// Move on to the next word, or `undefined` once the session is over
+    pub fn next_word(&mut self) -> Option<String> {
+        self.current = self.queue.pop_front();
+        self.current.clone()
+    }
+
+    /// Grade an answer to the current word, update its card and return the
+    /// `MatchResult`
+    pub fn submit_answer(&mut self, user_answer: &str) -> Result<JsValue, JsValue> {
+        let now_unix = (js_sys::Date::now() / 1000.0) as u64;
+        let result = self
+            .submit_answer_at(user_answer, now_unix)
+            .map_err(|e| JsValue::from_str(&e))?;
+
+        serde_wasm_bindgen::to_value(&result)
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+    }
+
+    /// True once every word of the session has been handed out
+    pub fn is_complete(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Current state of every card touched by the session, for saving
+    pub fn card_states(&self) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(&self.cards)
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize card states: {}", e)))
+    }
+}
+
+impl WasmQuizSession {
+    /// Native counterpart of `new`. Card states for words outside the
+    /// vocabulary are kept but never quizzed.
+    pub fn from_cards(
+        vocabulary: &[String],
+        cards: Vec<CardState>,
+        new_per_session: usize,
+    ) -> Self {
+        let known: HashMap<&str, &CardState> = cards.iter().map(|c| (c.word.as_str(), c)).collect();
+
+        let new_words: Vec<&String> = vocabulary
+            .iter()
+            .filter(|w| !known.contains_key(w.as_str()))
+            .take(new_per_session)
+            .collect();
+        let mut reviews: Vec<&CardState> = vocabulary
+            .iter()
+            .filter_map(|w| known.get(w.as_str()).copied())
+            .collect();
+        reviews.sort_by_key(|c| c.next_review_unix);
+
+        let mut queue = VecDeque::with_capacity(new_words.len() + reviews.len());
+        let mut new_iter = new_words.into_iter();
+        let mut review_iter = reviews.into_iter().map(|c| &c.word);
+        loop {
+            match (new_iter.next(), review_iter.next()) {
+                (None, None) => break,
+                (new, review) => queue.extend(new.into_iter().chain(review).cloned()),
+            }
+        }
+
+        WasmQuizSession {
+            queue,
+            cards,
+            current: None,
+        }
+    }
+
+    /// Native counterpart of `submit_answer` reviewing at `now_unix`. The
+    /// similarity maps to an SM-2 quality of `round(similarity * 5)`.
+    pub fn submit_answer_at(
+        &mut self,
+        user_answer: &str,
+        now_unix: u64,
+    ) -> Result<MatchResult, String> {
+        let word = self
+            .current
+            .clone()
+            .ok_or("No word to grade, call next_word first")?;
+        let result = MatchResult::from_pair(user_answer, &word);
+        let quality = (result.similarity * 5.0).round() as u8;
+
+        let index = match self.cards.iter().position(|c| c.word == word) {
+            Some(index) => index,
+            None => {
+                self.cards.push(CardState::new(&word, now_unix));
+                self.cards.len() - 1
+            }
+        };
+        let mut card = sm2_review(&self.cards[index], quality, now_unix);
+        card.mastery += MASTERY_SMOOTHING * (result.similarity - card.mastery);
+        self.cards[index] = card;
+
+        Ok(result)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StudySchedule {
+    /// Reviews to do per day to finish before the deadline
+    pub words_per_day: usize,
+    /// Whether SM-2's spacing lets every word reach the target in time
+    pub achievable: bool,
+    /// Average mastery reached by the reviews that fit before the deadline
+    pub projected_mastery: f64,
+}
+
+/// Plan reviews for a whole vocabulary of fresh cards at `current_avg_mastery`.
+/// Each word is reviewed (with quality 4) on the days SM-2 schedules until it
+/// reaches `target_mastery`; the reviews are spread evenly over the days left,
+/// all on one day when none are left. Since `words_per_day` is rounded up, it
+/// stops falling at 1 once there are at least as many days as reviews.
+fn study_schedule(
+    vocab_size: usize,
+    current_avg_mastery: f64,
+    target_mastery: f64,
+    days_remaining: u32,
+) -> StudySchedule {
+    let target = target_mastery.clamp(0.0, 1.0);
+    let mut mastery = current_avg_mastery.clamp(0.0, 1.0);
+    let mut projected_mastery = mastery;
+    let mut card = CardState::new("", 0);
+    let mut review_day: u64 = 0;
+    let mut last_review_day: u64 = 0;
+    let mut reviews = 0;
+
+    while mastery < target && reviews < MAX_SIMULATED_SESSIONS {
+        card = sm2_review(&card, SIMULATED_QUALITY, card.next_review_unix);
+        mastery = mastery_after_review(mastery, &card);
+        if review_day < days_remaining as u64 {
+            projected_mastery = mastery;
+        }
+        last_review_day = review_day;
+        review_day += card.interval as u64;
+        reviews += 1;
+    }
+
+    let total_reviews = vocab_size * reviews as usize;
+    StudySchedule {
+        words_per_day: total_reviews.div_ceil(days_remaining.max(1) as usize),
+        achievable: days_remaining > 0
+            && mastery >= target
+            && last_review_day < days_remaining as u64,
+        projected_mastery,
+    }
+}
+
+/// Suggest how many reviews a day get a vocabulary from its current average
+/// mastery to a target before an exam, as `{ words_per_day, achievable,
+/// projected_mastery }`
+#[wasm_bindgen]
+pub fn compute_study_schedule(
+    vocab_size: usize,
+    current_avg_mastery: f64,
+    target_mastery: f64,
+    days_remaining: u32,
+) -> JsValue {
+    let schedule = study_schedule(
+        vocab_size,
+        current_avg_mastery,
+        target_mastery,
+        days_remaining,
+    );
+    serde_wasm_bindgen::to_value(&schedule).unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words;
+
+    const TODAY: u64 = 1_700_000_000;
+
+    fn card(word: &str, mastery: f64, next_review_unix: u64) -> CardState {
+        CardState {
+            word: word.to_string(),
+            ease_factor: 2.5,
+            interval: 1,
+            repetitions: 1,
+            next_review_unix,
+            mastery,
+        }
+    }
+
+    #[test]
+    fn test_study_plan_due_only() {
+        let vocab = words(&["cat", "dog", "owl", "fox"]);
+        let states = vec![
+            card("cat", 0.9, TODAY - SECONDS_PER_DAY),
+            card("dog", 0.2, TODAY),
+            card("owl", 0.1, TODAY + SECONDS_PER_DAY),
+            card("fox", 0.5, TODAY - 3 * SECONDS_PER_DAY),
+        ];
+        assert_eq!(
+            study_plan(&vocab, &states, 10, TODAY),
+            vec!["dog", "fox", "cat"]
+        );
+        assert_eq!(study_plan(&vocab, &states, 2, TODAY), vec!["dog", "fox"]);
+        assert!(study_plan(&vocab, &states, 0, TODAY).is_empty());
+    }
+
+    #[test]
+    fn test_study_plan_overdue_first() {
+        let vocab = words(&["cat", "dog"]);
+        let states = vec![
+            card("cat", 0.5, TODAY),
+            card("dog", 0.5, TODAY - 2 * SECONDS_PER_DAY),
+        ];
+        assert_eq!(study_plan(&vocab, &states, 10, TODAY), vec!["dog", "cat"]);
+    }
+
+    #[test]
+    fn test_study_plan_new_words() {
+        let vocab = words(&["cat", "new"]);
+        let states = vec![card("cat", 0.3, TODAY), card("gone", 0.0, TODAY)];
+        assert_eq!(study_plan(&vocab, &states, 10, TODAY), vec!["new", "cat"]);
+    }
+
+    #[test]
+    fn test_sm2_review() {
+        let new_card = CardState {
+            repetitions: 0,
+            interval: 0,
+            ..card("cat", 0.0, TODAY)
+        };
+        let first = sm2_review(&new_card, 5, TODAY);
+        assert_eq!((first.repetitions, first.interval), (1, 1));
+        assert_eq!(first.next_review_unix, TODAY + SECONDS_PER_DAY);
+        assert!((first.ease_factor - 2.6).abs() < 1e-12);
+
+        let second = sm2_review(&first, 4, TODAY + SECONDS_PER_DAY);
+        assert_eq!((second.repetitions, second.interval), (2, 6));
+        let third = sm2_review(&second, 4, TODAY + 7 * SECONDS_PER_DAY);
+        assert_eq!(third.interval, (6.0 * second.ease_factor).round() as u32);
+
+        let failed = sm2_review(&third, 1, TODAY + 30 * SECONDS_PER_DAY);
+        assert_eq!((failed.repetitions, failed.interval), (0, 1));
+        assert!(failed.ease_factor >= MIN_EASE_FACTOR);
+
+        // Timestamps from JS near u64::MAX saturate instead of overflowing
+        assert_eq!(sm2_review(&third, 4, u64::MAX).next_review_unix, u64::MAX);
+    }
+
+    #[test]
+    fn test_learning_sessions() {
+        let state = card("cat", 0.0, TODAY);
+        let from_zero = learning_sessions(0.0, &state);
+        let from_high = learning_sessions(0.8, &state);
+        assert!(from_zero > from_high);
+        assert!(from_high > 0);
+        assert_eq!(learning_sessions(0.9, &state), 0);
+        assert_eq!(learning_sessions(1.0, &state), 0);
+    }
+
+    #[test]
+    fn test_quiz_session_interleaves() {
+        let vocab = words(&["new1", "rev1", "new2", "rev2", "new3", "rev3"]);
+        let states = vec![
+            card("rev1", 0.5, TODAY),
+            card("rev2", 0.5, TODAY - SECONDS_PER_DAY),
+            card("rev3", 0.5, TODAY + SECONDS_PER_DAY),
+        ];
+        let mut session = WasmQuizSession::from_cards(&vocab, states, 2);
+
+        let mut order = Vec::new();
+        assert!(!session.is_complete());
+        while let Some(word) = session.next_word() {
+            order.push(word);
+        }
+        assert_eq!(order, vec!["new1", "rev2", "new2", "rev1", "rev3"]);
+        assert!(session.is_complete());
+    }
+
+    #[test]
+    fn test_quiz_session_completes_after_all_words() {
+        let vocab = words(&["a1", "a2", "b1", "b2", "b3"]);
+        let states = vec![
+            card("b1", 0.2, TODAY),
+            card("b2", 0.2, TODAY),
+            card("b3", 0.2, TODAY),
+        ];
+        let mut session = WasmQuizSession::from_cards(&vocab, states, 2);
+        for _ in 0..4 {
+            assert!(session.next_word().is_some());
+            assert!(!session.is_complete());
+        }
+        assert!(session.next_word().is_some());
+        assert!(session.is_complete());
+        assert_eq!(session.next_word(), None);
+    }
+
+    #[test]
+    fn test_quiz_session_submit_answer() {
+        let vocab = words(&["house", "cat"]);
+        let mut session = WasmQuizSession::from_cards(&vocab, vec![card("cat", 0.4, TODAY)], 1);
+        assert!(session.submit_answer_at("house", TODAY).is_err());
+
+        assert_eq!(session.next_word().as_deref(), Some("house"));
+        let result = session.submit_answer_at("house", TODAY).unwrap();
+        assert!(result.is_exact);
+        let house = session.cards.iter().find(|c| c.word == "house").unwrap();
+        assert_eq!(house.repetitions, 1);
+        assert_eq!(house.next_review_unix, TODAY + SECONDS_PER_DAY);
+        assert_eq!(house.mastery, 0.5);
+
+        assert_eq!(session.next_word().as_deref(), Some("cat"));
+        session.submit_answer_at("dog", TODAY).unwrap();
+        let cat = session.cards.iter().find(|c| c.word == "cat").unwrap();
+        assert_eq!(cat.repetitions, 0);
+        assert!(cat.mastery < 0.4);
+    }
+
+    #[test]
+    fn test_study_schedule_more_days_fewer_words() {
+        let per_day = |days| study_schedule(5, 0.2, 0.9, days).words_per_day;
+        let mut previous = per_day(1);
+        for days in [2, 5, 10, 30, 60, 1000] {
+            let current = per_day(days);
+            assert!(
+                current <= previous,
+                "{} days: {} >= {}",
+                days,
+                current,
+                previous
+            );
+            previous = current;
+        }
+        assert!(per_day(2) < per_day(1));
+        assert_eq!(per_day(1000), 1);
+    }
+
+    #[test]
+    fn test_study_schedule_deadline() {
+        let none_left = study_schedule(100, 0.5, 0.9, 0);
+        assert!(!none_left.achievable);
+        assert_eq!(none_left.projected_mastery, 0.5);
+
+        let plenty = study_schedule(100, 0.5, 0.9, 60);
+        assert!(plenty.achievable);
+        assert!(plenty.projected_mastery >= 0.9);
+
+        // SM-2 spaces the later reviews out, so two days can't cover them
+        let rushed = study_schedule(100, 0.0, 0.99, 2);
+        assert!(!rushed.achievable);
+        assert!(rushed.projected_mastery < 0.99);
+
+        assert!(study_schedule(100, 0.95, 0.9, 1).achievable);
+    }
+}
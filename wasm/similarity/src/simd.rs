@@ -0,0 +1,201 @@
+//! SSE4.2 Levenshtein distance for native x86_64 builds.
+//!
+//! Cells on the same anti-diagonal of the DP matrix don't depend on each
+//! other, so 16 of them are computed at once with saturating `u8` lanes.
+//! Distances therefore have to fit in a byte, which limits the fast path to
+//! ASCII strings of at most 255 bytes; everything else uses the scalar DP.
+
+use std::arch::x86_64::*;
+
+/// Cells computed per vector
+const LANES: usize = 16;
+/// Longest input whose distance is guaranteed to fit in a `u8` lane
+const MAX_SIMD_LEN: usize = u8::MAX as usize;
+
+/// Distance via the vectorized kernel, or `None` when the inputs or the
+/// CPU don't support the fast path.
+pub(crate) fn try_levenshtein(s1: &str, s2: &str) -> Option<usize> {
+    if !s1.is_ascii() || !s2.is_ascii() || s1.len() > MAX_SIMD_LEN || s2.len() > MAX_SIMD_LEN {
+        return None;
+    }
+    if !is_x86_feature_detected!("sse4.2") {
+        return None;
+    }
+    // Safety: the required CPU feature was detected above
+    Some(unsafe { levenshtein_simd(s1.as_bytes(), s2.as_bytes()) })
+}
+
+/// Levenshtein distance over bytes computed one anti-diagonal at a time.
+/// Both inputs must be at most 255 bytes long.
+///
+/// # Safety
+///
+/// The CPU must support SSE4.2.
+#[target_feature(enable = "sse4.2")]
+pub(crate) unsafe fn levenshtein_simd(s1: &[u8], s2: &[u8]) -> usize {
+    let (n, m) = (s1.len(), s2.len());
+    debug_assert!(n <= MAX_SIMD_LEN && m <= MAX_SIMD_LEN);
+    if n == 0 {
+        return m;
+    }
+    if m == 0 {
+        return n;
+    }
+
+    // Diagonals are indexed by row `i`; padding lets the last vector of a
+    // diagonal run past its end without leaving the buffer.
+    let width = n + 1 + LANES;
+    let mut prev2 = vec![0u8; width];
+    let mut prev = vec![0u8; width];
+    let mut cur = vec![0u8; width];
+
+    let mut a = s1.to_vec();
+    a.resize(n + LANES, 0);
+    // Reversing `s2` makes `s2[d - i - 1]` contiguous as `i` grows: it is `rev[m - d + i]`
+    let mut rev: Vec<u8> = s2.iter().rev().copied().collect();
+    rev.resize(m + LANES, 0);
+
+    let ones = _mm_set1_epi8(1);
+    // Diagonal 0 is D(0, 0) = 0; diagonal 1 is D(0, 1) = D(1, 0) = 1
+    prev[0] = 1;
+    prev[1] = 1;
+
+    for d in 2..=n + m {
+        // Interior cells i in [lo, hi], with j = d - i in [1, m]
+        let lo = d.saturating_sub(m).max(1);
+        let hi = n.min(d - 1);
+        let mut i = lo;
+        while i <= hi {
+            let up = _mm_loadu_si128(prev.as_ptr().add(i - 1) as *const __m128i);
+            let left = _mm_loadu_si128(prev.as_ptr().add(i) as *const __m128i);
+            let diag = _mm_loadu_si128(prev2.as_ptr().add(i - 1) as *const __m128i);
+            let ac = _mm_loadu_si128(a.as_ptr().add(i - 1) as *const __m128i);
+            let bc = _mm_loadu_si128(rev.as_ptr().add(m + i - d) as *const __m128i);
+
+            let cost = _mm_andnot_si128(_mm_cmpeq_epi8(ac, bc), ones);
+            let value = _mm_min_epu8(
+                _mm_min_epu8(_mm_adds_epu8(up, ones), _mm_adds_epu8(left, ones)),
+                _mm_adds_epu8(diag, cost),
+            );
+            _mm_storeu_si128(cur.as_mut_ptr().add(i) as *mut __m128i, value);
+            i += LANES;
+        }
+
+        // First row and column, written after the vectors that may overrun them
+        if d <= m {
+            cur[0] = d as u8;
+        }
+        if d <= n {
+            cur[d] = d as u8;
+        }
+
+        std::mem::swap(&mut prev2, &mut prev);
+        std::mem::swap(&mut prev, &mut cur);
+    }
+
+    prev[n] as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levenshtein_scalar;
+    use std::time::Instant;
+
+    /// Deterministic pseudo-random ASCII strings over a small alphabet so
+    /// that pairs share plenty of characters.
+    fn random_strings(count: usize, max_len: usize, seed: u64) -> Vec<String> {
+        let alphabet = b"abcdeXYZ ";
+        let mut state = seed;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        (0..count)
+            .map(|_| {
+                let len = (next() % (max_len as u64 + 1)) as usize;
+                (0..len)
+                    .map(|_| alphabet[(next() % alphabet.len() as u64) as usize] as char)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_simd_agrees_with_scalar() {
+        if !is_x86_feature_detected!("sse4.2") {
+            return;
+        }
+        let strings = random_strings(120, 70, 0x9e37_79b9_7f4a_7c15);
+        for pair in strings.chunks(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            let simd = unsafe { levenshtein_simd(a.as_bytes(), b.as_bytes()) };
+            assert_eq!(simd, levenshtein_scalar(a, b), "{:?} vs {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_simd_edge_cases() {
+        if !is_x86_feature_detected!("sse4.2") {
+            return;
+        }
+        let long_a = "a".repeat(255);
+        let long_b = "b".repeat(255);
+        let cases = [
+            ("", ""),
+            ("", "abc"),
+            ("abc", ""),
+            ("a", "a"),
+            ("a", "b"),
+            ("kitten", "sitting"),
+            ("flaw", "lawn"),
+            (long_a.as_str(), long_b.as_str()),
+            (long_a.as_str(), "a"),
+        ];
+        for (a, b) in cases {
+            let simd = unsafe { levenshtein_simd(a.as_bytes(), b.as_bytes()) };
+            assert_eq!(simd, levenshtein_scalar(a, b), "{:?} vs {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_try_levenshtein_fallback() {
+        assert_eq!(try_levenshtein("café", "cafe"), None);
+        assert_eq!(try_levenshtein(&"a".repeat(256), "a"), None);
+    }
+
+    #[test]
+    #[ignore = "benchmark; run with `cargo test --release -- --ignored`"]
+    fn bench_simd_speedup() {
+        if !is_x86_feature_detected!("sse4.2") {
+            return;
+        }
+        let strings = random_strings(200, 100, 42);
+        let pairs: Vec<(&str, &str)> = strings
+            .iter()
+            .zip(strings.iter().skip(1))
+            .map(|(a, b)| (a.as_str(), b.as_str()))
+            .collect();
+
+        let start = Instant::now();
+        let scalar: usize = pairs.iter().map(|(a, b)| levenshtein_scalar(a, b)).sum();
+        let scalar_time = start.elapsed();
+
+        let start = Instant::now();
+        let simd: usize = pairs
+            .iter()
+            .map(|(a, b)| unsafe { levenshtein_simd(a.as_bytes(), b.as_bytes()) })
+            .sum();
+        let simd_time = start.elapsed();
+
+        assert_eq!(scalar, simd);
+        let speedup = scalar_time.as_secs_f64() / simd_time.as_secs_f64();
+        println!(
+            "scalar {:?}, simd {:?}, speedup {:.1}x",
+            scalar_time, simd_time, speedup
+        );
+        assert!(speedup >= 3.0, "speedup only {:.1}x", speedup);
+    }
+}
@@ -0,0 +1,450 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::{best_match, levenshtein_distance, similarity_score, MatchResult};
+
+/// British endings and their American spelling
+type Endings = &'static [(&'static str, &'static str)];
+
+/// British endings with their American spelling, each paired with the stems
+/// it is rewritten after. Curated because every ending is also native to
+/// many words ("advise", "filled", "science", "mediocre"); a stem matches the
+/// end of what precedes the ending, so "met" covers "kilometre".
+const SUFFIX_RULES: &[(Endings, &[&str])] = &[
+    (ISE_ENDINGS, ISE_STEMS),
+    (YSE_ENDINGS, YSE_STEMS),
+    (OGUE_ENDINGS, OGUE_STEMS),
+    (AMME_ENDINGS, AMME_STEMS),
+    (ENCE_ENDINGS, ENCE_STEMS),
+    (DOUBLED_L_ENDINGS, DOUBLED_L_STEMS),
+    (RE_ENDINGS, RE_STEMS),
+];
+
+const ISE_ENDINGS: Endings = &[
+    ("isations", "izations"),
+    ("isation", "ization"),
+    ("ising", "izing"),
+    ("ised", "ized"),
+    ("isers", "izers"),
+    ("iser", "izer"),
+    ("ises", "izes"),
+    ("ise", "ize"),
+];
+
+const ISE_STEMS: &[&str] = &[
+    "apolog", "author", "capital", "categor", "central", "civil", "colon", "critic", "emphas",
+    "final", "global", "harmon", "ideal", "legal", "local", "maxim", "memor", "minim", "mobil",
+    "modern", "national", "natural", "normal", "optim", "organ", "patron", "personal", "priorit",
+    "public", "real", "recogn", "special", "stabil", "standard", "summar", "symbol", "sympath",
+    "util", "visual",
+];
+
+const YSE_ENDINGS: Endings = &[
+    ("ysing", "yzing"),
+    ("ysed", "yzed"),
+    ("ysers", "yzers"),
+    ("yser", "yzer"),
+    ("yse", "yze"),
+];
+
+const YSE_STEMS: &[&str] = &["anal", "catal", "dial", "electrol", "hydrol", "paral"];
+
+const OGUE_ENDINGS: Endings = &[("ogues", "ogs"), ("ogue", "og")];
+
+const OGUE_STEMS: &[&str] = &[
+    "anal", "catal", "demag", "dial", "epil", "monol", "pedag", "prol", "travel",
+];
+
+const AMME_ENDINGS: Endings = &[("ammes", "ams"), ("amme", "am")];
+
+const AMME_STEMS: &[&str] = &["gr", "progr"];
+
+const ENCE_ENDINGS: Endings = &[("celess", "seless"), ("ces", "ses"), ("ce", "se")];
+
+const ENCE_STEMS: &[&str] = &["defen", "licen", "offen", "preten"];
+
+/// Inflections doubling a stem's final "l" in British spelling ("travelled")
+const DOUBLED_L_ENDINGS: Endings = &[
+    ("lers", "ers"),
+    ("ler", "er"),
+    ("led", "ed"),
+    ("ling", "ing"),
+];
+
+const DOUBLED_L_STEMS: &[&str] = &[
+    "cancel", "channel", "counsel", "dial", "duel", "equal", "fuel", "jewel", "label", "level",
+    "marvel", "model", "panel", "pencil", "quarrel", "rival", "shovel", "signal", "total",
+    "travel", "tunnel",
+];
+
+const RE_ENDINGS: Endings = &[
+    ("ring", "ering"),
+    ("red", "ered"),
+    ("res", "ers"),
+    ("re", "er"),
+];
+
+const RE_STEMS: &[&str] = &[
+    "calib", "cent", "fib", "goit", "lit", "lust", "maneuv", "meag", "met", "sab", "saltpet",
+    "scept", "somb", "spect", "theat", "tit",
+];
+
+/// Stems whose British "ae"/"oe" digraph is simplified in American spelling
+/// ("anaemia", "oestrogen"). Curated because the digraphs are native to most
+/// words that contain them ("shoe", "canoe", "aerial").
+const DIGRAPH_STEMS: &[(&str, &str)] = &[
+    ("amoeb", "ameb"),
+    ("anaem", "anem"),
+    ("anaesth", "anesth"),
+    ("archaeo", "archeo"),
+    ("caesar", "cesar"),
+    ("coeliac", "celiac"),
+    ("diarrhoe", "diarrhe"),
+    ("encyclopaed", "encycloped"),
+    ("faec", "fec"),
+    ("foet", "fet"),
+    ("gonorrhoe", "gonorrhe"),
+    ("haem", "hem"),
+    ("homoeo", "homeo"),
+    ("leukaem", "leukem"),
+    ("manoeuv", "maneuv"),
+    ("mediaev", "mediev"),
+    ("oesoph", "esoph"),
+    ("oestr", "estr"),
+    ("paed", "ped"),
+    ("palaeo", "paleo"),
+];
+
+/// Minimum length of a word before "our" becomes "or", keeping "four" and
+/// "hour" intact
+const MIN_OUR_PREFIX: usize = 2;
+
+/// What may follow "our" for it to become "or": the end of the word
+/// ("colour"), a plural, or a common derivation ("favourite", "coloured")
+const OUR_ENDINGS: &[&str] = &["", "s", "ite", "able", "ed", "ing"];
+
+/// Words whose "our" is spelled the same in American English
+const NATIVE_OUR_WORDS: &[&str] = &[
+    "amour",
+    "contour",
+    "detour",
+    "devour",
+    "flour",
+    "glamour",
+    "scour",
+    "troubadour",
+    "velour",
+];
+
+/// Length both words need before a single edit counts as a spelling variant
+const MIN_FUZZY_VARIANT_LENGTH: usize = 6;
+
+/// Rewrite a British spelling into its American form using the common
+/// patterns (our→or, and in known stems ise→ize, yse→yze, re→er, ence→ense,
+/// ogue→og, amme→am, doubled l→l, ae→e and oe→e). Returns `None` when
+/// nothing applies.
+pub(crate) fn british_to_american(word: &str) -> Option<String> {
+    let lower = word.to_lowercase();
+    let mut result = lower.clone();
+
+    if let Some(pos) = result.find("our") {
+        let (stem, ending) = result.split_at(pos + 3);
+        if pos >= MIN_OUR_PREFIX
+            && OUR_ENDINGS.contains(&ending)
+            && !NATIVE_OUR_WORDS.iter().any(|native| stem.ends_with(native))
+        {
+            result.replace_range(pos..pos + 3, "or");
+        }
+    }
+
+    if let Some((british, american)) = DIGRAPH_STEMS
+        .iter()
+        .find(|(british, _)| result.contains(british))
+    {
+        result = result.replacen(british, american, 1);
+    }
+
+    let rewrite = SUFFIX_RULES.iter().find_map(|(endings, stems)| {
+        endings.iter().find_map(|(british, american)| {
+            let stem = result.strip_suffix(british)?;
+            stems
+                .iter()
+                .any(|known| stem.ends_with(known))
+                .then(|| format!("{}{}", stem, american))
+        })
+    });
+    if let Some(rewritten) = rewrite {
+        result = rewritten;
+    }
+
+    (result != lower).then_some(result)
+}
+
+fn american_form(word: &str) -> String {
+    british_to_american(word).unwrap_or_else(|| word.to_lowercase())
+}
+
+/// Whether `longer` is `shorter` with one extra vowel
+fn is_vowel_insertion(longer: &str, shorter: &str) -> bool {
+    let longer: Vec<char> = longer.chars().collect();
+    let shorter: Vec<char> = shorter.chars().collect();
+    if longer.len() != shorter.len() + 1 {
+        return false;
+    }
+    let split = longer
+        .iter()
+        .zip(&shorter)
+        .take_while(|(a, b)| a == b)
+        .count();
+    "aeiouy".contains(longer[split]) && longer[split + 1..] == shorter[split..]
+}
+
+/// Vocabulary words that are spellings of the same word: equal once both are
+/// rewritten to American spelling, or (for longer words) a single edit apart
+/// after that rewrite. A single edit only counts if a British pattern was
+/// rewritten or it is a vowel insertion, so "battle"/"bottle" don't match.
+/// The word itself is never included.
+fn spelling_variants(word: &str, vocabulary: &[String]) -> Vec<String> {
+    let target = american_form(word);
+    let lower = word.to_lowercase();
+    let word_rewritten = british_to_american(word).is_some();
+
+    let mut variants: Vec<String> = Vec::new();
+    for candidate in vocabulary {
+        if candidate.to_lowercase() == lower || variants.contains(candidate) {
+            continue;
+        }
+        let form = american_form(candidate);
+        let rewritten = word_rewritten || british_to_american(candidate).is_some();
+        let close = target.chars().count() >= MIN_FUZZY_VARIANT_LENGTH
+            && form.chars().count() >= MIN_FUZZY_VARIANT_LENGTH
+            && levenshtein_distance(&target, &form) <= 1
+            && (rewritten
+                || is_vowel_insertion(&target, &form)
+                || is_vowel_insertion(&form, &target));
+        if form == target || close {
+            variants.push(candidate.clone());
+        }
+    }
+    variants
+}
+
+/// Find vocabulary words that are regional spelling variants of `word`
+/// ("colour"/"color", "realise"/"realize")
+#[wasm_bindgen]
+pub fn find_spelling_variants(word: &str, vocabulary: JsValue) -> Result<JsValue, JsValue> {
+    let vocab_vec: Vec<String> = serde_wasm_bindgen::from_value(vocabulary)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse vocabulary: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&spelling_variants(word, &vocab_vec))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+/// Split a compound into vocabulary words by repeatedly taking the longest
+/// vocabulary word that prefixes the rest (case-insensitive). When the word
+/// can't be covered completely it is returned whole, as a single component.
+pub(crate) fn decompose_compound(word: &str, vocabulary: &[String]) -> Vec<String> {
+    let lower = word.to_lowercase();
+    let known: Vec<String> = vocabulary
+        .iter()
+        .map(|w| w.to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let mut components = Vec::new();
+    let mut rest = lower.as_str();
+    while !rest.is_empty() {
+        match known
+            .iter()
+            .filter(|w| rest.starts_with(w.as_str()))
+            .max_by_key(|w| w.len())
+        {
+            Some(part) => {
+                components.push(part.clone());
+                rest = &rest[part.len()..];
+            }
+            None => return vec![word.to_string()],
+        }
+    }
+
+    if components.is_empty() {
+        vec![word.to_string()]
+    } else {
+        components
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CompoundMatch {
+    pub decomposition: Vec<String>,
+    /// Best vocabulary match of each component, in the same order
+    pub matches: Vec<MatchResult>,
+}
+
+fn compound_matches(query: &str, vocabulary: &[String]) -> CompoundMatch {
+    let decomposition = decompose_compound(query, vocabulary);
+    let matches = decomposition
+        .iter()
+        .filter_map(|part| best_match(part, vocabulary))
+        .collect();
+    CompoundMatch {
+        decomposition,
+        matches,
+    }
+}
+
+/// Split a compound query ("notebook") into vocabulary words and match each
+/// part, returning `{ decomposition, matches }`
+#[wasm_bindgen]
+pub fn find_compound_word_matches(query: &str, vocabulary: JsValue) -> Result<JsValue, JsValue> {
+    let vocab_vec: Vec<String> = serde_wasm_bindgen::from_value(vocabulary)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse vocabulary: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&compound_matches(query, &vocab_vec))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+/// Prefixes removed by `strip_english_affixes`, longest first
+const AFFIX_PREFIXES: &[&str] = &["pre", "un", "re"];
+/// Suffixes removed by `strip_english_affixes`, longest first
+const AFFIX_SUFFIXES: &[&str] = &["tion", "ing", "ed", "er", "ly"];
+/// Shortest stem left after removing an affix, so "red" stays "red"
+const MIN_STEM_LENGTH: usize = 3;
+
+/// Crude English stem: drop the longest matching prefix and the longest
+/// matching suffix (case-insensitive), each only if at least three chars
+/// remain ("walked" → "walk", "unhappily" → "happi").
+fn strip_english_affixes(word: &str) -> String {
+    let mut stem = word.to_lowercase();
+    if let Some(prefix) = AFFIX_PREFIXES
+        .iter()
+        .find(|p| stem.starts_with(*p) && stem.chars().count() - p.len() >= MIN_STEM_LENGTH)
+    {
+        stem.drain(..prefix.len());
+    }
+    if let Some(suffix) = AFFIX_SUFFIXES
+        .iter()
+        .find(|s| stem.ends_with(*s) && stem.chars().count() - s.len() >= MIN_STEM_LENGTH)
+    {
+        stem.truncate(stem.len() - suffix.len());
+    }
+    stem
+}
+
+/// Calculate similarity of the stems left after stripping common English
+/// prefixes and suffixes ("walked" and "walking" both become "walk")
+#[wasm_bindgen]
+pub fn calculate_stem_similarity(s1: &str, s2: &str) -> f64 {
+    similarity_score(&strip_english_affixes(s1), &strip_english_affixes(s2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words;
+
+    #[test]
+    fn test_british_to_american() {
+        let cases = [
+            ("colour", "color"),
+            ("favourite", "favorite"),
+            ("neighbours", "neighbors"),
+            ("honourable", "honorable"),
+            ("coloured", "colored"),
+            ("realise", "realize"),
+            ("organisation", "organization"),
+            ("analyse", "analyze"),
+            ("centre", "center"),
+            ("defence", "defense"),
+            ("catalogue", "catalog"),
+            ("programme", "program"),
+            ("travelled", "traveled"),
+            ("kilometres", "kilometers"),
+            ("licences", "licenses"),
+            ("modelling", "modeling"),
+            ("anaemia", "anemia"),
+            ("manoeuvre", "maneuver"),
+            ("oestrogen", "estrogen"),
+            ("paediatric", "pediatric"),
+        ];
+        for (british, american) in cases {
+            assert_eq!(
+                british_to_american(british).as_deref(),
+                Some(american),
+                "{}",
+                british
+            );
+        }
+    }
+
+    #[test]
+    fn test_british_to_american_unchanged() {
+        for word in [
+            "four", "hour", "color", "are", "fire", "wise", "cat", "", "shoe", "canoe", "does",
+            "heroes", "aerial", "detour", "flourish", "devour", "contours", "filled", "filling",
+            "killer", "seller", "advise", "noise", "premise", "science", "mediocre", "cadre",
+            "rogue",
+        ] {
+            assert_eq!(british_to_american(word), None, "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_spelling_variants() {
+        let vocab = words(&["color", "realize", "colour", "cooler", "mustache", "collar"]);
+        assert_eq!(spelling_variants("colour", &vocab), vec!["color"]);
+        assert_eq!(spelling_variants("color", &vocab), vec!["colour"]);
+        assert_eq!(spelling_variants("realise", &vocab), vec!["realize"]);
+        assert_eq!(spelling_variants("moustache", &vocab), vec!["mustache"]);
+        assert!(spelling_variants("table", &vocab).is_empty());
+        assert!(spelling_variants("canoe", &words(&["cane"])).is_empty());
+        assert!(spelling_variants("filled", &words(&["filed"])).is_empty());
+        assert!(spelling_variants("advise", &words(&["advice"])).is_empty());
+        assert!(spelling_variants("premise", &words(&["promise", "precise"])).is_empty());
+        let lookalikes = words(&["bottle", "wonder", "bitter", "private"]);
+        for word in ["battle", "wander", "better", "pirate"] {
+            assert!(spelling_variants(word, &lookalikes).is_empty(), "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_decompose_compound() {
+        let vocab = words(&["note", "book", "no", "sun", "flower", "sunflower"]);
+        assert_eq!(decompose_compound("notebook", &vocab), vec!["note", "book"]);
+        assert_eq!(decompose_compound("Notebook", &vocab), vec!["note", "book"]);
+        assert_eq!(decompose_compound("sunflower", &vocab), vec!["sunflower"]);
+        assert_eq!(decompose_compound("table", &vocab), vec!["table"]);
+        assert_eq!(decompose_compound("notebooks", &vocab), vec!["notebooks"]);
+    }
+
+    #[test]
+    fn test_compound_matches() {
+        let vocab = words(&["note", "book", "table"]);
+        let result = compound_matches("notebook", &vocab);
+        assert_eq!(result.decomposition, vec!["note", "book"]);
+        assert_eq!(result.matches.len(), 2);
+        assert!(result.matches.iter().all(|m| m.is_exact));
+
+        let single = compound_matches("tables", &vocab);
+        assert_eq!(single.decomposition, vec!["tables"]);
+        assert_eq!(single.matches[0].word, "table");
+    }
+
+    #[test]
+    fn test_strip_english_affixes() {
+        assert_eq!(strip_english_affixes("walked"), "walk");
+        assert_eq!(strip_english_affixes("walking"), "walk");
+        assert_eq!(strip_english_affixes("Preheated"), "heat");
+        assert_eq!(strip_english_affixes("nation"), "nation");
+        assert_eq!(strip_english_affixes("red"), "red");
+        assert_eq!(strip_english_affixes("unkindly"), "kind");
+    }
+
+    #[test]
+    fn test_stem_similarity() {
+        assert_eq!(calculate_stem_similarity("walked", "walking"), 1.0);
+        assert!(
+            calculate_stem_similarity("running", "runner") > similarity_score("running", "runner")
+        );
+    }
+}
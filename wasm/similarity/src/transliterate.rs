@@ -0,0 +1,169 @@
+use wasm_bindgen::prelude::*;
+
+use crate::similarity_score;
+
+/// Latin spelling of a lowercase Russian Cyrillic letter (simplified BGN/PCGN)
+fn cyrillic(c: char) -> Option<&'static str> {
+    Some(match c {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' => "g",
+        'д' => "d",
+        'е' => "e",
+        'ё' => "yo",
+        'ж' => "zh",
+        'з' => "z",
+        'и' => "i",
+        'й' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ы' => "y",
+        'э' => "e",
+        'ю' => "yu",
+        'я' => "ya",
+        _ => return None,
+    })
+}
+
+/// Latin spelling of a lowercase Greek letter (ELOT 743 without digraph rules)
+fn greek(c: char) -> Option<&'static str> {
+    Some(match c {
+        'α' | 'ά' => "a",
+        'β' => "v",
+        'γ' => "g",
+        'δ' => "d",
+        'ε' | 'έ' => "e",
+        'ζ' => "z",
+        'η' | 'ή' => "i",
+        'θ' => "th",
+        'ι' | 'ί' | 'ϊ' => "i",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "x",
+        'ο' | 'ό' => "o",
+        'π' => "p",
+        'ρ' => "r",
+        'σ' | 'ς' => "s",
+        'τ' => "t",
+        'υ' | 'ύ' | 'ϋ' => "y",
+        'φ' => "f",
+        'χ' => "ch",
+        'ψ' => "ps",
+        'ω' | 'ώ' => "o",
+        _ => return None,
+    })
+}
+
+/// Latin spelling of an Arabic consonant; short vowels are unwritten so
+/// only the long vowel letters produce vowels
+fn arabic(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ا' | 'أ' | 'إ' | 'آ' => "a",
+        'ب' => "b",
+        'ت' | 'ة' => "t",
+        'ث' => "th",
+        'ج' => "j",
+        'ح' => "h",
+        'خ' => "kh",
+        'د' => "d",
+        'ذ' => "dh",
+        'ر' => "r",
+        'ز' => "z",
+        'س' => "s",
+        'ش' => "sh",
+        'ص' => "s",
+        'ض' => "d",
+        'ط' => "t",
+        'ظ' => "z",
+        'ع' | 'ء' => "'",
+        'غ' => "gh",
+        'ف' => "f",
+        'ق' => "q",
+        'ك' => "k",
+        'ل' => "l",
+        'م' => "m",
+        'ن' => "n",
+        'ه' => "h",
+        'و' => "w",
+        'ي' | 'ى' => "y",
+        _ => return None,
+    })
+}
+
+/// Spell Cyrillic, Greek and Arabic text in Latin letters so it can be
+/// compared with romanized answers. Capital letters give a capitalized
+/// transliteration ("Щ" → "Shch"); anything without a rule is kept as is.
+pub(crate) fn transliterate_to_latin(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        match cyrillic(lower)
+            .or_else(|| greek(lower))
+            .or_else(|| arabic(lower))
+        {
+            Some(latin) if lower != c => {
+                let mut letters = latin.chars();
+                if let Some(first) = letters.next() {
+                    out.extend(first.to_uppercase());
+                    out.push_str(letters.as_str());
+                }
+            }
+            Some(latin) => out.push_str(latin),
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+/// Calculate similarity after transliterating both strings to Latin script
+/// (for comparing "кот" with "kot")
+#[wasm_bindgen]
+pub fn calculate_transliterated_similarity(s1: &str, s2: &str) -> f64 {
+    similarity_score(&transliterate_to_latin(s1), &transliterate_to_latin(s2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cyrillic() {
+        assert_eq!(transliterate_to_latin("кот"), "kot");
+        assert_eq!(calculate_transliterated_similarity("кот", "kot"), 1.0);
+        assert_eq!(calculate_transliterated_similarity("привет", "privet"), 1.0);
+        assert_eq!(transliterate_to_latin("Щука"), "Shchuka");
+    }
+
+    #[test]
+    fn test_greek_and_arabic() {
+        assert_eq!(transliterate_to_latin("λόγος"), "logos");
+        assert_eq!(transliterate_to_latin("Αθήνα"), "Athina");
+        assert_eq!(transliterate_to_latin("كتاب"), "ktab");
+    }
+
+    #[test]
+    fn test_unmapped_chars_pass_through() {
+        assert_eq!(transliterate_to_latin("cat 42!"), "cat 42!");
+        assert_eq!(transliterate_to_latin("日本"), "日本");
+        assert_eq!(transliterate_to_latin("кот-猫"), "kot-猫");
+        assert!(calculate_transliterated_similarity("cat", "кот") < 1.0);
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{similarity_score, MatchResult};
+
+#[derive(Default)]
+struct TrieNode {
+    children: BTreeMap<char, TrieNode>,
+    /// Set when a vocabulary word ends at this node
+    word: Option<String>,
+}
+
+/// Prefix tree over a vocabulary, used to prune fuzzy lookups
+#[derive(Default)]
+pub struct Trie {
+    root: TrieNode,
+    len: usize,
+}
+
+impl Trie {
+    pub fn new() -> Self {
+        Trie::default()
+    }
+
+    pub fn from_words(words: &[String]) -> Self {
+        let mut trie = Trie::new();
+        for word in words {
+            trie.insert(word);
+        }
+        trie
+    }
+
+    /// Insert a word, returning false if it was already present
+    pub fn insert(&mut self, word: &str) -> bool {
+        let mut node = &mut self.root;
+        for c in word.chars() {
+            node = node.children.entry(c).or_default();
+        }
+        if node.word.is_some() {
+            return false;
+        }
+        node.word = Some(word.to_string());
+        self.len += 1;
+        true
+    }
+
+    /// Number of distinct words stored
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// Find all words within `max_dist` edits of `query`.
+/// Walks the trie depth-first carrying one row of the Levenshtein matrix per
+/// node and skips any subtree whose row has no entry within `max_dist`.
+/// Results are sorted by similarity (descending).
+pub(crate) fn prefix_fuzzy_search(trie: &Trie, query: &str, max_dist: usize) -> Vec<MatchResult> {
+    let query_chars: Vec<char> = query.chars().collect();
+    let first_row: Vec<usize> = (0..=query_chars.len()).collect();
+
+    let mut matches = Vec::new();
+    if let Some(word) = &trie.root.word {
+        if query_chars.len() <= max_dist {
+            matches.push(MatchResult {
+                word: word.clone(),
+                distance: query_chars.len(),
+                similarity: similarity_score(query, word),
+                is_exact: query == word,
+            });
+        }
+    }
+    for (&c, child) in &trie.root.children {
+        search_node(
+            child,
+            c,
+            &query_chars,
+            query,
+            &first_row,
+            max_dist,
+            &mut matches,
+        );
+    }
+
+    matches.sort();
+    matches
+}
+
+fn search_node(
+    node: &TrieNode,
+    c: char,
+    query_chars: &[char],
+    query: &str,
+    prev_row: &[usize],
+    max_dist: usize,
+    matches: &mut Vec<MatchResult>,
+) {
+    let mut row = Vec::with_capacity(prev_row.len());
+    row.push(prev_row[0] + 1);
+    for j in 1..prev_row.len() {
+        let cost = if query_chars[j - 1] == c { 0 } else { 1 };
+        let value = (prev_row[j] + 1) // deletion
+            .min(row[j - 1] + 1) // insertion
+            .min(prev_row[j - 1] + cost); // substitution
+        row.push(value);
+    }
+
+    let distance = row[row.len() - 1];
+    if let Some(word) = &node.word {
+        if distance <= max_dist {
+            matches.push(MatchResult {
+                word: word.clone(),
+                distance,
+                similarity: similarity_score(query, word),
+                is_exact: distance == 0,
+            });
+        }
+    }
+
+    if row.iter().min().is_some_and(|&min| min <= max_dist) {
+        for (&next, child) in &node.children {
+            search_node(child, next, query_chars, query, &row, max_dist, matches);
+        }
+    }
+}
+
+/// Vocabulary stored as a prefix tree for fast fuzzy lookups
+#[wasm_bindgen]
+pub struct WasmTrie {
+    trie: Trie,
+}
+
+#[wasm_bindgen]
+impl WasmTrie {
+    /// Build a trie from an array of words
+    #[wasm_bindgen(constructor)]
+    pub fn new(words: JsValue) -> Result<WasmTrie, JsValue> {
+        let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+        Ok(WasmTrie {
+            trie: Trie::from_words(&words_vec),
+        })
+    }
+
+    /// Add a word, returning false if it was already present
+    pub fn insert(&mut self, word: &str) -> bool {
+        self.trie.insert(word)
+    }
+
+    /// Number of distinct words stored
+    pub fn len(&self) -> usize {
+        self.trie.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trie.is_empty()
+    }
+
+    /// Find all words within `max_dist` edits of `query`
+    pub fn fuzzy_search(&self, query: &str, max_dist: usize) -> Result<JsValue, JsValue> {
+        let matches = prefix_fuzzy_search(&self.trie, query, max_dist);
+
+        serde_wasm_bindgen::to_value(&matches)
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{levenshtein_distance, words};
+    use std::collections::BTreeSet;
+
+    /// Deterministic vocabulary of short words over a small alphabet so that
+    /// many words fall within a couple of edits of each other.
+    fn generated_vocabulary(size: usize) -> Vec<String> {
+        let alphabet: Vec<char> = "abcdeilnorst".chars().collect();
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        let mut words = BTreeSet::new();
+        while words.len() < size {
+            let len = 3 + (next() % 5) as usize;
+            let word: String = (0..len)
+                .map(|_| alphabet[(next() % alphabet.len() as u64) as usize])
+                .collect();
+            words.insert(word);
+        }
+        words.into_iter().collect()
+    }
+
+    #[test]
+    fn test_fuzzy_search_matches_brute_force() {
+        let vocab = generated_vocabulary(1_000);
+        let trie = Trie::from_words(&vocab);
+        assert_eq!(trie.len(), 1_000);
+
+        for query in ["stone", "lid", "crate", "aaaa", ""] {
+            let found: BTreeSet<String> = prefix_fuzzy_search(&trie, query, 2)
+                .into_iter()
+                .map(|m| m.word)
+                .collect();
+            let expected: BTreeSet<String> = vocab
+                .iter()
+                .filter(|w| levenshtein_distance(query, w) <= 2)
+                .cloned()
+                .collect();
+            assert_eq!(found, expected, "query {:?}", query);
+        }
+    }
+
+    #[test]
+    fn test_fuzzy_search_distances() {
+        let vocab = words(&["cat", "cart", "bat", "dog"]);
+        let trie = Trie::from_words(&vocab);
+
+        let results = prefix_fuzzy_search(&trie, "cat", 1);
+        let words: Vec<&str> = results.iter().map(|m| m.word.as_str()).collect();
+        assert_eq!(words[0], "cat");
+        assert_eq!(results.len(), 3);
+        for m in &results {
+            assert_eq!(m.distance, levenshtein_distance("cat", &m.word));
+        }
+    }
+
+    #[test]
+    fn test_insert_duplicate() {
+        let mut trie = Trie::new();
+        assert!(trie.insert("cat"));
+        assert!(!trie.insert("cat"));
+        assert_eq!(trie.len(), 1);
+    }
+}
@@ -0,0 +1,121 @@
+use wasm_bindgen::prelude::*;
+
+use crate::similarity_score;
+
+/// Unicode blocks a comparison can be restricted to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnicodeBlock {
+    BasicLatin,
+    Latin1Supplement,
+    LatinExtendedA,
+    GreekAndCoptic,
+    Cyrillic,
+    Hebrew,
+    Arabic,
+    Devanagari,
+    Hiragana,
+    Katakana,
+    CjkUnifiedIdeographs,
+    HangulSyllables,
+}
+
+impl UnicodeBlock {
+    /// Parse a block name as passed from JS, ignoring case (`"BasicLatin"`,
+    /// `"Hiragana"`, `"CJKUnifiedIdeographs"`, ...)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "basiclatin" => Some(UnicodeBlock::BasicLatin),
+            "latin1supplement" => Some(UnicodeBlock::Latin1Supplement),
+            "latinextendeda" => Some(UnicodeBlock::LatinExtendedA),
+            "greekandcoptic" | "greek" => Some(UnicodeBlock::GreekAndCoptic),
+            "cyrillic" => Some(UnicodeBlock::Cyrillic),
+            "hebrew" => Some(UnicodeBlock::Hebrew),
+            "arabic" => Some(UnicodeBlock::Arabic),
+            "devanagari" => Some(UnicodeBlock::Devanagari),
+            "hiragana" => Some(UnicodeBlock::Hiragana),
+            "katakana" => Some(UnicodeBlock::Katakana),
+            "cjkunifiedideographs" => Some(UnicodeBlock::CjkUnifiedIdeographs),
+            "hangulsyllables" => Some(UnicodeBlock::HangulSyllables),
+            _ => None,
+        }
+    }
+
+    /// First and last code point of the block
+    fn range(&self) -> (char, char) {
+        match self {
+            UnicodeBlock::BasicLatin => ('\u{0000}', '\u{007F}'),
+            UnicodeBlock::Latin1Supplement => ('\u{0080}', '\u{00FF}'),
+            UnicodeBlock::LatinExtendedA => ('\u{0100}', '\u{017F}'),
+            UnicodeBlock::GreekAndCoptic => ('\u{0370}', '\u{03FF}'),
+            UnicodeBlock::Cyrillic => ('\u{0400}', '\u{04FF}'),
+            UnicodeBlock::Hebrew => ('\u{0590}', '\u{05FF}'),
+            UnicodeBlock::Arabic => ('\u{0600}', '\u{06FF}'),
+            UnicodeBlock::Devanagari => ('\u{0900}', '\u{097F}'),
+            UnicodeBlock::Hiragana => ('\u{3040}', '\u{309F}'),
+            UnicodeBlock::Katakana => ('\u{30A0}', '\u{30FF}'),
+            UnicodeBlock::CjkUnifiedIdeographs => ('\u{4E00}', '\u{9FFF}'),
+            UnicodeBlock::HangulSyllables => ('\u{AC00}', '\u{D7AF}'),
+        }
+    }
+
+    fn contains(&self, c: char) -> bool {
+        let (first, last) = self.range();
+        (first..=last).contains(&c)
+    }
+}
+
+/// `similarity_score` of the characters of each string that lie in `block`
+fn similarity_in_block(s1: &str, s2: &str, block: UnicodeBlock) -> f64 {
+    let keep = |s: &str| -> String { s.chars().filter(|&c| block.contains(c)).collect() };
+    similarity_score(&keep(s1), &keep(s2))
+}
+
+/// Calculate similarity looking only at characters from one Unicode block,
+/// e.g. the kana of a mixed Japanese answer
+#[wasm_bindgen]
+pub fn calculate_similarity_unicode_block(s1: &str, s2: &str, block: &str) -> Result<f64, JsValue> {
+    let block = UnicodeBlock::parse(block)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown Unicode block: {}", block)))?;
+
+    Ok(similarity_in_block(s1, s2, block))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similarity_in_block() {
+        assert_eq!(
+            similarity_in_block("hello世界", "hello", UnicodeBlock::BasicLatin),
+            1.0
+        );
+        assert_eq!(
+            similarity_in_block("hello世界", "bye世界", UnicodeBlock::CjkUnifiedIdeographs),
+            1.0
+        );
+        // One substitution over six bytes of kana, normalized like
+        // `calculate_similarity`
+        assert_eq!(
+            similarity_in_block("ねこcat", "ねご", UnicodeBlock::Hiragana),
+            1.0 - 1.0 / 6.0
+        );
+        assert_eq!(
+            similarity_in_block("abc", "xyz", UnicodeBlock::Cyrillic),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_parse_block() {
+        assert_eq!(
+            UnicodeBlock::parse("BasicLatin"),
+            Some(UnicodeBlock::BasicLatin)
+        );
+        assert_eq!(
+            UnicodeBlock::parse("CJKUnifiedIdeographs"),
+            Some(UnicodeBlock::CjkUnifiedIdeographs)
+        );
+        assert_eq!(UnicodeBlock::parse("Klingon"), None);
+    }
+}
@@ -0,0 +1,1166 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::autocomplete::prefix_edit_distance;
+use crate::{match_words, similarity_score, CompareOptions, MatchResult};
+
+/// Number of most recent scores averaged into a word's mastery
+const MASTERY_WINDOW: usize = 5;
+/// Seed used until `set_seed` is called, so quiz order is reproducible
+const DEFAULT_SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+/// Word limit of vocabularies built without `with_capacity`, keeping a huge
+/// list from exhausting WASM memory
+const DEFAULT_MAX_WORDS: usize = 100_000;
+/// Most edits between a root and the start of a word in its family
+const WORD_FAMILY_MAX_DISTANCE: usize = 2;
+/// Similarity at which two words are tagged as confusable in exports
+const CONFUSION_THRESHOLD: f64 = 0.7;
+/// Anki file headers (`#key:value` lines) preceding the exported rows
+const ANKI_HEADER: &str =
+    "#separator:comma\n#html:false\n#columns:front,back,tags\n#tags column:3\n";
+
+/// SplitMix64 generator: tiny, seedable and good enough for picking quiz words.
+#[derive(Clone)]
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform float in [0, 1)
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform index in [0, len)
+    fn next_index(&mut self, len: usize) -> usize {
+        (self.next_u64() % len as u64) as usize
+    }
+}
+
+/// Mastery from which a word counts as mastered in `quiz_statistics`
+const MASTERED_THRESHOLD: f64 = 0.9;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuizStatistics {
+    pub total_words: usize,
+    /// Words with mastery of at least 0.9
+    pub words_mastered: usize,
+    pub words_in_progress: usize,
+    /// Words with mastery 0.0
+    pub words_not_started: usize,
+    pub average_mastery: f64,
+    /// Word with the lowest mastery, `None` for an empty vocabulary
+    pub hardest_word: Option<String>,
+    pub easiest_word: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct VocabularyEntry {
+    word: String,
+    /// Similarity of every recorded answer, oldest first
+    scores: Vec<f64>,
+}
+
+impl VocabularyEntry {
+    fn new(word: &str) -> Self {
+        VocabularyEntry {
+            word: word.to_string(),
+            scores: Vec::new(),
+        }
+    }
+
+    /// Average of the most recent scores, 0.0 for a word never practised
+    fn mastery(&self) -> f64 {
+        let recent = &self.scores[self.scores.len().saturating_sub(MASTERY_WINDOW)..];
+        if recent.is_empty() {
+            return 0.0;
+        }
+        recent.iter().sum::<f64>() / recent.len() as f64
+    }
+
+    /// Least-squares slope of the scores against answer number; 0.0 with
+    /// fewer than two scores
+    fn trend(&self) -> f64 {
+        let n = self.scores.len() as f64;
+        if self.scores.len() < 2 {
+            return 0.0;
+        }
+        let mean_x = (n - 1.0) / 2.0;
+        let mean_y = self.scores.iter().sum::<f64>() / n;
+        let (covariance, variance) =
+            self.scores
+                .iter()
+                .enumerate()
+                .fold((0.0, 0.0), |(cov, var), (i, &y)| {
+                    let dx = i as f64 - mean_x;
+                    (cov + dx * (y - mean_y), var + dx * dx)
+                });
+        covariance / variance
+    }
+}
+
+/// Format version written by `snapshot`; bump it when `VocabularySnapshot` changes
+const SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Deserialize)]
+struct SnapshotHeader {
+    snapshot_version: u32,
+}
+
+/// Saved words and answer history, restorable with `restore`
+#[derive(Clone, Serialize, Deserialize)]
+pub struct VocabularySnapshot {
+    snapshot_version: u32,
+    entries: Vec<VocabularyEntry>,
+}
+
+fn check_snapshot_version(version: u32) -> Result<(), String> {
+    if version != SNAPSHOT_VERSION {
+        return Err(format!(
+            "Unsupported snapshot version {} (expected {})",
+            version, SNAPSHOT_VERSION
+        ));
+    }
+    Ok(())
+}
+
+/// A learner's word list together with their answer history
+#[wasm_bindgen]
+pub struct WasmVocabulary {
+    entries: Vec<VocabularyEntry>,
+    /// Position of each word in `entries`
+    index: HashMap<String, usize>,
+    rng: SplitMix64,
+    /// `entries.len()` never exceeds this
+    max_words: usize,
+}
+
+#[wasm_bindgen]
+impl WasmVocabulary {
+    /// Create a vocabulary from an array of words (duplicates are dropped).
+    /// Fails if there are more than 100,000 distinct words.
+    #[wasm_bindgen(constructor)]
+    pub fn new(words: JsValue) -> Result<WasmVocabulary, JsValue> {
+        let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+        WasmVocabulary::from_words(words_vec).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Create a vocabulary holding at most `max_words` words
+    pub fn with_capacity(words: JsValue, max_words: usize) -> Result<WasmVocabulary, JsValue> {
+        let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+        WasmVocabulary::with_max_words(words_vec, max_words).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Most words this vocabulary accepts
+    pub fn max_words(&self) -> usize {
+        self.max_words
+    }
+
+    /// Number of words in the vocabulary
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Check whether a word is in the vocabulary
+    pub fn contains(&self, word: &str) -> bool {
+        self.entry(word).is_some()
+    }
+
+    /// Similarity of two vocabulary words, or `undefined` if either is missing
+    pub fn confusion_score(&self, word1: &str, word2: &str) -> Option<f64> {
+        (self.contains(word1) && self.contains(word2)).then(|| similarity_score(word1, word2))
+    }
+
+    /// Add a word (a no-op if it is already present). Fails when the
+    /// vocabulary is full.
+    pub fn add_word(&mut self, word: &str) -> Result<(), JsValue> {
+        self.insert_word(word)
+            .map(|_| ())
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Add the words of `other` that are missing here, with their answer
+    /// history. Nothing is added if they wouldn't all fit.
+    pub fn merge(&mut self, other: &WasmVocabulary) -> Result<(), JsValue> {
+        self.merge_from(other).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Find fuzzy matches for `query` among the vocabulary words
+    pub fn find_matches(&self, query: &str, threshold: f64) -> Result<JsValue, JsValue> {
+        let matches = self.matches_for(query, threshold);
+
+        serde_wasm_bindgen::to_value(&matches)
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+    }
+
+    /// Grade a learner's answer for `word` and record its similarity.
+    /// Returns the recorded score, or `undefined` if the word is unknown.
+    pub fn record_answer(&mut self, word: &str, user_answer: &str) -> Option<f64> {
+        let score = similarity_score(user_answer, word);
+        self.record_score(word, score).then_some(score)
+    }
+
+    /// Record a similarity score (0.0 to 1.0) for `word`, returning false if
+    /// the word is unknown
+    pub fn record_score(&mut self, word: &str, similarity: f64) -> bool {
+        match self.index.get(word) {
+            Some(&i) => {
+                let entry = &mut self.entries[i];
+                entry.scores.push(similarity.clamp(0.0, 1.0));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Mastery of a word in [0, 1]: the average of its recent scores.
+    /// Unknown or never practised words have mastery 0.0.
+    pub fn mastery_score(&self, word: &str) -> f64 {
+        self.entry(word).map_or(0.0, VocabularyEntry::mastery)
+    }
+
+    /// Every recorded score for a word, oldest first (empty if unknown)
+    pub fn score_history(&self, word: &str) -> Vec<f64> {
+        self.entry(word).map_or_else(Vec::new, |e| e.scores.clone())
+    }
+
+    /// Slope of a word's score history per answer: positive when improving,
+    /// 0.0 for unknown words or fewer than two answers
+    pub fn score_trend(&self, word: &str) -> f64 {
+        self.entry(word).map_or(0.0, VocabularyEntry::trend)
+    }
+
+    /// Words with mastery below `threshold`, weakest first (ties keep
+    /// vocabulary order)
+    pub fn words_below_mastery(&self, threshold: f64) -> Vec<String> {
+        let mut weak: Vec<(f64, &str)> = self
+            .entries
+            .iter()
+            .map(|e| (e.mastery(), e.word.as_str()))
+            .filter(|(mastery, _)| *mastery < threshold)
+            .collect();
+        weak.sort_by(|a, b| a.0.total_cmp(&b.0));
+        weak.into_iter().map(|(_, word)| word.to_string()).collect()
+    }
+
+    /// Reseed the generator used by `random_quiz_word` and `biased_quiz_word`
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = SplitMix64::new(seed);
+    }
+
+    /// Pick a random word, each with equal probability
+    pub fn random_quiz_word(&mut self) -> Option<String> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let index = self.rng.next_index(self.entries.len());
+        Some(self.entries[index].word.clone())
+    }
+
+    /// Pick a random word weighted by `1 - mastery`, so words the learner
+    /// struggles with come up more often. Falls back to a uniform pick once
+    /// every word is fully mastered.
+    pub fn biased_quiz_word(&mut self) -> Option<String> {
+        let weights: Vec<f64> = self.entries.iter().map(|e| 1.0 - e.mastery()).collect();
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return self.random_quiz_word();
+        }
+
+        let mut target = self.rng.next_f64() * total;
+        for (entry, weight) in self.entries.iter().zip(&weights) {
+            if target < *weight {
+                return Some(entry.word.clone());
+            }
+            target -= weight;
+        }
+        // Rounding can leave a sliver past the last weight
+        self.entries
+            .iter()
+            .zip(&weights)
+            .rev()
+            .find(|(_, &w)| w > 0.0)
+            .map(|(e, _)| e.word.clone())
+    }
+
+    /// New vocabulary holding only the words whose length in characters lies
+    /// within `[min_len, max_len]`, with their answer history. An inverted
+    /// range yields an empty vocabulary.
+    pub fn filter_by_length(&self, min_len: usize, max_len: usize) -> WasmVocabulary {
+        let mut vocabulary = WasmVocabulary {
+            entries: self
+                .entries
+                .iter()
+                .filter(|e| (min_len..=max_len).contains(&e.word.chars().count()))
+                .cloned()
+                .collect(),
+            index: HashMap::new(),
+            rng: self.rng.clone(),
+            max_words: self.max_words,
+        };
+        vocabulary.reindex();
+        vocabulary
+    }
+
+    /// The vocabulary words in their current order
+    pub fn words(&self) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(&self.word_list())
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize words: {}", e)))
+    }
+
+    /// The word at `index`, or `undefined` if out of bounds
+    pub fn word_at(&self, index: usize) -> Option<String> {
+        self.entries.get(index).map(|e| e.word.clone())
+    }
+
+    /// Reorder words by descending frequency from a `{ word: frequency }`
+    /// object. Words missing from the map keep their relative order at the end.
+    pub fn sort_by_frequency(&mut self, freq_map: JsValue) -> Result<(), JsValue> {
+        let freq_map: HashMap<String, f64> = serde_wasm_bindgen::from_value(freq_map)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse frequency map: {}", e)))?;
+
+        self.sort_by_frequency_map(&freq_map);
+        Ok(())
+    }
+
+    /// Words grouped by lowercased first character as a `{ letter: words }`
+    /// object; words not starting with a letter go under `"#"`
+    pub fn group_by_first_letter(&self) -> Result<JsValue, JsValue> {
+        self.first_letter_groups()
+            .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .map_err(|e| JsValue::from_str(&format!("Failed to serialize groups: {}", e)))
+    }
+
+    /// Precompute `find_matches` results for each prefix the learner is
+    /// expected to type, for instant incremental search
+    pub fn precompute_query_cache(
+        &self,
+        prefixes: JsValue,
+        threshold: f64,
+    ) -> Result<WasmQueryCache, JsValue> {
+        let prefixes: Vec<String> = serde_wasm_bindgen::from_value(prefixes)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse prefixes: {}", e)))?;
+
+        Ok(self.query_cache(&prefixes, threshold))
+    }
+
+    /// Flashcards as an Anki-importable CSV with `front,back,tags` columns:
+    /// the word, an empty back and the words it is easily confused with
+    pub fn export_anki_csv(&self) -> String {
+        let mut csv = String::from(ANKI_HEADER);
+        for entry in &self.entries {
+            let confusable: Vec<&str> = self
+                .entries
+                .iter()
+                .filter(|other| {
+                    other.word != entry.word
+                        && similarity_score(&entry.word, &other.word) >= CONFUSION_THRESHOLD
+                })
+                .map(|other| other.word.as_str())
+                .collect();
+            csv.push_str(&csv_field(&entry.word));
+            csv.push_str(",,");
+            csv.push_str(&csv_field(&confusable.join(";")));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Aggregate progress for a dashboard as `{ total_words, words_mastered,
+    /// words_in_progress, words_not_started, average_mastery, hardest_word,
+    /// easiest_word }`
+    pub fn quiz_statistics(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.statistics()).unwrap_or(JsValue::NULL)
+    }
+
+    /// Words of the same family as `root`: sharing its first `prefix_length`
+    /// chars and starting within two edits of it ("run" → "running", "runner").
+    /// With `prefix_length` 0 this is a fuzzy prefix search.
+    pub fn find_word_family(&self, root: &str, prefix_length: usize) -> Vec<String> {
+        self.entries
+            .iter()
+            .map(|e| &e.word)
+            .filter(|word| {
+                let shared = root
+                    .chars()
+                    .zip(word.chars())
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                shared >= prefix_length
+                    && prefix_edit_distance(root, word) <= WORD_FAMILY_MAX_DISTANCE
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Save the words and their answer history (e.g. before an edit, for undo)
+    pub fn snapshot(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.to_snapshot()).unwrap_or(JsValue::NULL)
+    }
+
+    /// Replace the words and history with a blob from `snapshot`. Blobs from
+    /// another format version are rejected and leave the vocabulary unchanged.
+    pub fn restore(&mut self, snapshot: JsValue) -> Result<(), JsValue> {
+        let header: SnapshotHeader = serde_wasm_bindgen::from_value(snapshot.clone())
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse snapshot: {}", e)))?;
+        check_snapshot_version(header.snapshot_version).map_err(|e| JsValue::from_str(&e))?;
+        let snapshot: VocabularySnapshot = serde_wasm_bindgen::from_value(snapshot)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse snapshot: {}", e)))?;
+
+        self.restore_snapshot(snapshot)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+}
+
+impl WasmVocabulary {
+    /// Create a vocabulary from native strings (duplicates are dropped)
+    pub fn from_words(words: Vec<String>) -> Result<Self, String> {
+        Self::with_max_words(words, DEFAULT_MAX_WORDS)
+    }
+
+    /// Native counterpart of `with_capacity`. Fails at the first distinct
+    /// word past `max_words`, so an oversized list costs at most
+    /// `max_words + 1` entries and one hash lookup per word.
+    pub fn with_max_words(words: Vec<String>, max_words: usize) -> Result<Self, String> {
+        let capacity = words.len().min(max_words);
+        let mut vocabulary = WasmVocabulary {
+            entries: Vec::with_capacity(capacity),
+            index: HashMap::with_capacity(capacity),
+            rng: SplitMix64::new(DEFAULT_SEED),
+            max_words,
+        };
+        for word in words {
+            if vocabulary.index.contains_key(&word) {
+                continue;
+            }
+            vocabulary.check_fits(vocabulary.entries.len() + 1)?;
+            vocabulary.push_entry(VocabularyEntry::new(&word));
+        }
+        Ok(vocabulary)
+    }
+
+    /// Native counterpart of `add_word`, returning false if the word was
+    /// already present
+    pub fn insert_word(&mut self, word: &str) -> Result<bool, String> {
+        if self.contains(word) {
+            return Ok(false);
+        }
+        self.check_fits(self.entries.len() + 1)?;
+        self.push_entry(VocabularyEntry::new(word));
+        Ok(true)
+    }
+
+    /// Native counterpart of `merge`
+    pub fn merge_from(&mut self, other: &WasmVocabulary) -> Result<(), String> {
+        let missing: Vec<&VocabularyEntry> = other
+            .entries
+            .iter()
+            .filter(|e| !self.contains(&e.word))
+            .collect();
+        self.check_fits(self.entries.len() + missing.len())?;
+        for entry in missing {
+            self.push_entry(entry.clone());
+        }
+        Ok(())
+    }
+
+    /// Fail unless `total` words stay within `max_words`
+    fn check_fits(&self, total: usize) -> Result<(), String> {
+        if total > self.max_words {
+            return Err(format!("Vocabulary is limited to {} words", self.max_words));
+        }
+        Ok(())
+    }
+
+    /// Native counterpart of `sort_by_frequency`
+    pub fn sort_by_frequency_map(&mut self, freq_map: &HashMap<String, f64>) {
+        // Stable sort: missing words (None) go last, equal frequencies keep their order
+        self.entries.sort_by(|a, b| {
+            let fa = freq_map.get(&a.word);
+            let fb = freq_map.get(&b.word);
+            match (fa, fb) {
+                (Some(x), Some(y)) => y.total_cmp(x),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            }
+        });
+        self.reindex();
+    }
+
+    /// Native counterpart of `group_by_first_letter`, keeping vocabulary order
+    /// within each group
+    pub fn first_letter_groups(&self) -> HashMap<char, Vec<String>> {
+        let mut groups: HashMap<char, Vec<String>> = HashMap::new();
+        for entry in &self.entries {
+            let key = match entry.word.chars().next() {
+                Some(c) if c.is_alphabetic() => c.to_lowercase().next().unwrap_or(c),
+                _ => '#',
+            };
+            groups.entry(key).or_default().push(entry.word.clone());
+        }
+        groups
+    }
+
+    /// Native counterpart of `precompute_query_cache`
+    pub fn query_cache(&self, prefixes: &[String], threshold: f64) -> WasmQueryCache {
+        let results = prefixes
+            .iter()
+            .map(|prefix| (prefix.clone(), self.matches_for(prefix, threshold)))
+            .collect();
+        WasmQueryCache { results, misses: 0 }
+    }
+
+    fn matches_for(&self, query: &str, threshold: f64) -> Vec<MatchResult> {
+        let options = CompareOptions {
+            threshold,
+            ..CompareOptions::default()
+        };
+        match_words(query, &self.word_list(), &options)
+    }
+
+    /// Native counterpart of `quiz_statistics`. Ties for hardest or easiest
+    /// word go to the earlier word.
+    pub fn statistics(&self) -> QuizStatistics {
+        let masteries: Vec<(f64, &str)> = self
+            .entries
+            .iter()
+            .map(|e| (e.mastery(), e.word.as_str()))
+            .collect();
+        let count = |f: &dyn Fn(f64) -> bool| masteries.iter().filter(|(m, _)| f(*m)).count();
+
+        let hardest = masteries
+            .iter()
+            .reduce(|best, item| if item.0 < best.0 { item } else { best });
+        let easiest = masteries
+            .iter()
+            .reduce(|best, item| if item.0 > best.0 { item } else { best });
+
+        QuizStatistics {
+            total_words: masteries.len(),
+            words_mastered: count(&|m| m >= MASTERED_THRESHOLD),
+            words_in_progress: count(&|m| m > 0.0 && m < MASTERED_THRESHOLD),
+            words_not_started: count(&|m| m == 0.0),
+            average_mastery: if masteries.is_empty() {
+                0.0
+            } else {
+                masteries.iter().map(|(m, _)| m).sum::<f64>() / masteries.len() as f64
+            },
+            hardest_word: hardest.map(|(_, w)| w.to_string()),
+            easiest_word: easiest.map(|(_, w)| w.to_string()),
+        }
+    }
+
+    /// Native counterpart of `snapshot`
+    pub fn to_snapshot(&self) -> VocabularySnapshot {
+        VocabularySnapshot {
+            snapshot_version: SNAPSHOT_VERSION,
+            entries: self.entries.clone(),
+        }
+    }
+
+    /// Native counterpart of `restore`
+    pub fn restore_snapshot(&mut self, snapshot: VocabularySnapshot) -> Result<(), String> {
+        check_snapshot_version(snapshot.snapshot_version)?;
+        self.check_fits(snapshot.entries.len())?;
+        self.entries = snapshot.entries;
+        self.reindex();
+        Ok(())
+    }
+
+    fn entry(&self, word: &str) -> Option<&VocabularyEntry> {
+        self.index.get(word).map(|&i| &self.entries[i])
+    }
+
+    /// Append an entry for a word that isn't present yet
+    fn push_entry(&mut self, entry: VocabularyEntry) {
+        self.index.insert(entry.word.clone(), self.entries.len());
+        self.entries.push(entry);
+    }
+
+    /// Rebuild `index` after `entries` was replaced or reordered. A word
+    /// repeated in a hand-edited snapshot resolves to its first entry.
+    fn reindex(&mut self) {
+        self.index.clear();
+        for (i, entry) in self.entries.iter().enumerate() {
+            self.index.entry(entry.word.clone()).or_insert(i);
+        }
+    }
+
+    fn word_list(&self) -> Vec<String> {
+        self.entries.iter().map(|e| e.word.clone()).collect()
+    }
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break, or
+/// starts with `#` (which Anki would read as a header line).
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) || value.starts_with('#') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// `find_matches` results precomputed for a fixed set of query prefixes
+#[wasm_bindgen]
+pub struct WasmQueryCache {
+    results: HashMap<String, Vec<MatchResult>>,
+    misses: usize,
+}
+
+#[wasm_bindgen]
+impl WasmQueryCache {
+    /// Cached matches for `prefix`, or `undefined` (counted as a miss) if it
+    /// wasn't precomputed
+    pub fn lookup(&mut self, prefix: &str) -> Option<JsValue> {
+        self.lookup_matches(prefix)
+            .and_then(|matches| serde_wasm_bindgen::to_value(matches).ok())
+    }
+
+    /// Number of lookups for prefixes that weren't precomputed
+    pub fn miss_count(&self) -> usize {
+        self.misses
+    }
+}
+
+impl WasmQueryCache {
+    /// Native counterpart of `lookup`
+    pub fn lookup_matches(&mut self, prefix: &str) -> Option<&Vec<MatchResult>> {
+        let matches = self.results.get(prefix);
+        if matches.is_none() {
+            self.misses += 1;
+        }
+        matches
+    }
+}
+
+/// Words of `a` also in `b`, in `a`'s order without duplicates
+fn intersection(a: &[String], b: &[String]) -> Vec<String> {
+    let in_b: HashSet<&String> = b.iter().collect();
+    let mut seen = HashSet::new();
+    a.iter()
+        .filter(|w| in_b.contains(w) && seen.insert(*w))
+        .cloned()
+        .collect()
+}
+
+/// Words of `a` followed by the words of `b` not in `a`, without duplicates
+fn union(a: &[String], b: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    a.iter()
+        .chain(b)
+        .filter(|w| seen.insert(*w))
+        .cloned()
+        .collect()
+}
+
+/// Words present in both lists (exact comparison)
+#[wasm_bindgen]
+pub fn vocabulary_intersection(a: JsValue, b: JsValue) -> Result<JsValue, JsValue> {
+    let a_vec: Vec<String> = serde_wasm_bindgen::from_value(a)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+    let b_vec: Vec<String> = serde_wasm_bindgen::from_value(b)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&intersection(&a_vec, &b_vec))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+/// Words present in either list (exact comparison)
+#[wasm_bindgen]
+pub fn vocabulary_union(a: JsValue, b: JsValue) -> Result<JsValue, JsValue> {
+    let a_vec: Vec<String> = serde_wasm_bindgen::from_value(a)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+    let b_vec: Vec<String> = serde_wasm_bindgen::from_value(b)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&union(&a_vec, &b_vec))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize results: {}", e)))
+}
+
+/// Longest word in chars `validate_vocabulary` accepts
+const MAX_VALID_WORD_LENGTH: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VocabularyIssue {
+    pub index: usize,
+    pub word: String,
+    /// "empty", "duplicate", "too_long" or "null_byte"
+    pub issue: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VocabularyValidation {
+    pub is_valid: bool,
+    /// In list order; a word with several problems appears once per problem
+    pub issues: Vec<VocabularyIssue>,
+}
+
+/// Find entries that would make a word list misbehave. Repeats of a word are
+/// reported at every index after its first occurrence.
+fn vocabulary_issues(words: &[String]) -> VocabularyValidation {
+    let mut seen = HashSet::new();
+    let mut issues = Vec::new();
+    for (index, word) in words.iter().enumerate() {
+        let checks = [
+            (word.is_empty(), "empty"),
+            (!seen.insert(word.as_str()), "duplicate"),
+            (word.chars().count() > MAX_VALID_WORD_LENGTH, "too_long"),
+            (word.contains('\0'), "null_byte"),
+        ];
+        for (_, issue) in checks.iter().filter(|(failed, _)| *failed) {
+            issues.push(VocabularyIssue {
+                index,
+                word: word.clone(),
+                issue: issue.to_string(),
+            });
+        }
+    }
+
+    VocabularyValidation {
+        is_valid: issues.is_empty(),
+        issues,
+    }
+}
+
+/// Check a word list for empty, duplicate, overlong (over 100 chars) and
+/// NUL-containing entries, returning `{ is_valid, issues }`
+#[wasm_bindgen]
+pub fn validate_vocabulary(words: JsValue) -> Result<JsValue, JsValue> {
+    let words_vec: Vec<String> = serde_wasm_bindgen::from_value(words)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse words: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&vocabulary_issues(&words_vec))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words;
+
+    fn vocabulary(list: &[&str]) -> WasmVocabulary {
+        WasmVocabulary::from_words(words(list)).unwrap()
+    }
+
+    #[test]
+    fn test_mastery_score() {
+        let mut vocab = vocabulary(&["cat", "dog", "cat"]);
+        assert_eq!(vocab.len(), 2);
+        assert_eq!(vocab.mastery_score("cat"), 0.0);
+
+        assert_eq!(vocab.record_answer("cat", "cat"), Some(1.0));
+        assert_eq!(vocab.mastery_score("cat"), 1.0);
+        assert!(vocab.record_score("cat", 0.0));
+        assert_eq!(vocab.mastery_score("cat"), 0.5);
+
+        assert_eq!(vocab.record_answer("bird", "bird"), None);
+        assert_eq!(vocab.mastery_score("bird"), 0.0);
+    }
+
+    #[test]
+    fn test_random_quiz_word() {
+        let mut vocab = vocabulary(&["cat", "dog", "bird"]);
+        for _ in 0..20 {
+            let word = vocab.random_quiz_word().unwrap();
+            assert!(vocab.contains(&word));
+        }
+        assert_eq!(vocabulary(&[]).random_quiz_word(), None);
+        assert_eq!(vocabulary(&[]).biased_quiz_word(), None);
+    }
+
+    #[test]
+    fn test_biased_quiz_word_prefers_low_mastery() {
+        let mut vocab = vocabulary(&["weak", "strong"]);
+        vocab.record_score("weak", 0.1);
+        vocab.record_score("strong", 0.9);
+        vocab.set_seed(42);
+
+        let mut weak = 0;
+        for _ in 0..1_000 {
+            if vocab.biased_quiz_word().as_deref() == Some("weak") {
+                weak += 1;
+            }
+        }
+        assert!(weak > 800, "weak picked {} times", weak);
+    }
+
+    #[test]
+    fn test_seeded_quiz_words_repeat() {
+        let mut a = vocabulary(&["cat", "dog", "bird", "fish"]);
+        let mut b = vocabulary(&["cat", "dog", "bird", "fish"]);
+        a.set_seed(7);
+        b.set_seed(7);
+        for _ in 0..10 {
+            assert_eq!(a.biased_quiz_word(), b.biased_quiz_word());
+        }
+    }
+
+    #[test]
+    fn test_filter_by_length() {
+        let mut vocab = vocabulary(&["cat", "horse", "dog", "élan", "elephant"]);
+        vocab.record_score("cat", 0.8);
+
+        let three = vocab.filter_by_length(3, 3);
+        assert_eq!(three.word_list(), vec!["cat", "dog"]);
+        assert_eq!(three.mastery_score("cat"), 0.8);
+        assert!(three.contains("dog") && !three.contains("horse"));
+
+        assert_eq!(vocab.filter_by_length(4, 4).word_list(), vec!["élan"]);
+        assert_eq!(vocab.filter_by_length(0, 100).len(), vocab.len());
+        assert!(vocab.filter_by_length(5, 3).is_empty());
+    }
+
+    #[test]
+    fn test_sort_by_frequency() {
+        let mut vocab = vocabulary(&["zebra", "the", "cat", "quokka", "house"]);
+        let before = match_words("hose", &vocab.word_list(), &CompareOptions::default());
+
+        let freq: HashMap<String, f64> = [("the", 5000.0), ("house", 120.0), ("cat", 300.0)]
+            .iter()
+            .map(|(w, f)| (w.to_string(), *f))
+            .collect();
+        vocab.sort_by_frequency_map(&freq);
+
+        assert_eq!(
+            vocab.word_list(),
+            vec!["the", "cat", "house", "zebra", "quokka"]
+        );
+        let after = match_words("hose", &vocab.word_list(), &CompareOptions::default());
+        assert_eq!(before, after);
+
+        // Lookups follow the words to their new positions
+        assert!(vocab.record_score("house", 0.6));
+        assert_eq!(vocab.word_at(2), Some("house".to_string()));
+        assert_eq!(vocab.score_history("house"), vec![0.6]);
+        assert_eq!(vocab.mastery_score("zebra"), 0.0);
+    }
+
+    #[test]
+    fn test_words_round_trip() {
+        let list = words(&["cat", "dog", "bird"]);
+        let vocab = WasmVocabulary::from_words(list.clone()).unwrap();
+        assert_eq!(vocab.word_list(), list);
+        assert_eq!(
+            WasmVocabulary::from_words(vocab.word_list())
+                .unwrap()
+                .word_list(),
+            list
+        );
+    }
+
+    #[test]
+    fn test_word_at() {
+        let vocab = vocabulary(&["cat", "dog"]);
+        assert_eq!(vocab.word_at(0).as_deref(), Some("cat"));
+        assert_eq!(vocab.word_at(1).as_deref(), Some("dog"));
+        assert_eq!(vocab.word_at(vocab.len()), None);
+        assert_eq!(vocabulary(&[]).word_at(0), None);
+    }
+
+    #[test]
+    fn test_first_letter_groups() {
+        let vocab = vocabulary(&["apple", "Avocado", "banana", "über", "#tag", "42", "Ähre"]);
+        let groups = vocab.first_letter_groups();
+
+        assert_eq!(groups[&'a'], vec!["apple", "Avocado"]);
+        assert_eq!(groups[&'b'], vec!["banana"]);
+        assert_eq!(groups[&'ü'], vec!["über"]);
+        assert_eq!(groups[&'ä'], vec!["Ähre"]);
+        assert_eq!(groups[&'#'], vec!["#tag", "42"]);
+
+        let total: usize = groups.values().map(Vec::len).sum();
+        assert_eq!(total, vocab.len());
+    }
+
+    #[test]
+    fn test_first_letter_groups_empty() {
+        assert!(vocabulary(&[]).first_letter_groups().is_empty());
+    }
+
+    #[test]
+    fn test_query_cache() {
+        let vocab = vocabulary(&["cat", "cart", "car", "dog"]);
+        let prefixes: Vec<String> = ["c", "ca", "car"].iter().map(|p| p.to_string()).collect();
+        let mut cache = vocab.query_cache(&prefixes, 0.3);
+
+        for prefix in &prefixes {
+            let direct: Vec<String> = vocab
+                .matches_for(prefix, 0.3)
+                .into_iter()
+                .map(|m| m.word)
+                .collect();
+            let cached: Vec<String> = cache
+                .lookup_matches(prefix)
+                .unwrap()
+                .iter()
+                .map(|m| m.word.clone())
+                .collect();
+            assert_eq!(cached, direct);
+        }
+        assert_eq!(cache.miss_count(), 0);
+
+        assert!(cache.lookup_matches("do").is_none());
+        assert_eq!(cache.miss_count(), 1);
+    }
+
+    #[test]
+    fn test_confusion_score() {
+        let vocab = vocabulary(&["affect", "effect", "except"]);
+        assert_eq!(vocab.confusion_score("affect", "affect"), Some(1.0));
+        assert_eq!(
+            vocab.confusion_score("affect", "effect"),
+            Some(crate::calculate_similarity("affect", "effect"))
+        );
+        assert_eq!(vocab.confusion_score("affect", "accept"), None);
+        assert_eq!(vocab.confusion_score("accept", "accept"), None);
+    }
+
+    #[test]
+    fn test_score_history_and_trend() {
+        let mut vocab = vocabulary(&["cat", "dog", "owl"]);
+        for score in [0.2, 0.4, 0.6, 0.8] {
+            vocab.record_score("cat", score);
+            vocab.record_score("dog", 0.5);
+        }
+        assert_eq!(vocab.score_history("cat"), vec![0.2, 0.4, 0.6, 0.8]);
+        assert!((vocab.score_trend("cat") - 0.2).abs() < 1e-12);
+        assert_eq!(vocab.score_trend("dog"), 0.0);
+
+        assert!(vocab.score_history("owl").is_empty());
+        assert_eq!(vocab.score_trend("owl"), 0.0);
+        assert!(vocab.score_history("missing").is_empty());
+        assert_eq!(vocab.score_trend("missing"), 0.0);
+    }
+
+    #[test]
+    fn test_words_below_mastery() {
+        let mut vocab = vocabulary(&["cat", "dog", "owl"]);
+        vocab.record_score("cat", 0.9);
+        vocab.record_score("dog", 0.4);
+        assert_eq!(vocab.words_below_mastery(0.95), vec!["owl", "dog", "cat"]);
+        assert_eq!(vocab.words_below_mastery(0.5), vec!["owl", "dog"]);
+
+        vocab.insert_word("fox").unwrap();
+        assert!(vocab.words_below_mastery(0.01).contains(&"fox".to_string()));
+        assert!(vocab.words_below_mastery(0.0).is_empty());
+    }
+
+    #[test]
+    fn test_words_below_mastery_all_perfect() {
+        let mut vocab = vocabulary(&["cat", "dog"]);
+        for word in ["cat", "dog"] {
+            vocab.record_answer(word, word);
+        }
+        assert!(vocab.words_below_mastery(1.0).is_empty());
+        assert!(vocab.words_below_mastery(0.5).is_empty());
+    }
+
+    #[test]
+    fn test_export_anki_csv() {
+        let vocab = vocabulary(&["affect", "effect", "table", "well, yes", "say \"hi\""]);
+        let csv = vocab.export_anki_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        let header: Vec<&str> = lines
+            .iter()
+            .take_while(|l| l.starts_with('#'))
+            .copied()
+            .collect();
+        assert_eq!(
+            header,
+            vec![
+                "#separator:comma",
+                "#html:false",
+                "#columns:front,back,tags",
+                "#tags column:3"
+            ]
+        );
+        let rows = &lines[header.len()..];
+        assert_eq!(rows.len(), vocab.len());
+        assert_eq!(rows[0], "affect,,effect");
+        assert_eq!(rows[1], "effect,,affect");
+        assert_eq!(rows[2], "table,,");
+        assert_eq!(rows[3], "\"well, yes\",,");
+        assert_eq!(rows[4], "\"say \"\"hi\"\"\",,");
+    }
+
+    #[test]
+    fn test_statistics() {
+        let mut vocab = vocabulary(&["cat", "dog", "owl", "fox"]);
+        vocab.record_score("cat", 1.0);
+        vocab.record_score("dog", 0.5);
+        vocab.record_score("fox", 0.3);
+        let stats = vocab.statistics();
+
+        assert_eq!(stats.total_words, 4);
+        assert_eq!(
+            stats.total_words,
+            stats.words_mastered + stats.words_in_progress + stats.words_not_started
+        );
+        assert_eq!(stats.words_mastered, 1);
+        assert_eq!(stats.words_in_progress, 2);
+        assert_eq!(stats.words_not_started, 1);
+        assert!((stats.average_mastery - 0.45).abs() < 1e-12);
+        assert_eq!(stats.hardest_word.as_deref(), Some("owl"));
+        assert_eq!(stats.easiest_word.as_deref(), Some("cat"));
+    }
+
+    #[test]
+    fn test_statistics_empty() {
+        let stats = vocabulary(&[]).statistics();
+        assert_eq!(stats.total_words, 0);
+        assert_eq!(stats.average_mastery, 0.0);
+        assert!(stats.hardest_word.is_none());
+    }
+
+    #[test]
+    fn test_find_word_family() {
+        let vocab = vocabulary(&["run", "running", "runner", "sun", "table", "rung", "ran"]);
+        let family = vocab.find_word_family("run", 2);
+        assert_eq!(family, vec!["run", "running", "runner", "rung"]);
+        assert!(!family.contains(&"sun".to_string()));
+
+        let fuzzy = vocab.find_word_family("run", 0);
+        assert!(fuzzy.contains(&"sun".to_string()));
+        assert!(fuzzy.contains(&"ran".to_string()));
+        assert!(!fuzzy.contains(&"table".to_string()));
+
+        assert!(vocab.find_word_family("run", 10).is_empty());
+    }
+
+    #[test]
+    fn test_intersection_and_union() {
+        let a = words(&["cat", "bat"]);
+        let b = words(&["bat", "rat"]);
+        assert_eq!(intersection(&a, &b), words(&["bat"]));
+        assert_eq!(union(&a, &b), words(&["cat", "bat", "rat"]));
+
+        assert!(intersection(&a, &[]).is_empty());
+        assert_eq!(union(&a, &[]), a);
+        assert_eq!(union(&[], &b), b);
+        assert!(intersection(&words(&["Cat"]), &a).is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let mut vocab = vocabulary(&["cat", "dog"]);
+        vocab.insert_word("cart").unwrap();
+        vocab.record_answer("cat", "cta");
+        let snapshot = vocab.to_snapshot();
+        let before = vocab.matches_for("crt", 0.3);
+
+        vocab.insert_word("card").unwrap();
+        vocab.record_answer("cat", "cat");
+        vocab.restore_snapshot(snapshot).unwrap();
+
+        let after = vocab.matches_for("crt", 0.3);
+        assert_eq!(before.len(), after.len());
+        for (a, b) in before.iter().zip(&after) {
+            assert_eq!((&a.word, a.similarity), (&b.word, b.similarity));
+        }
+        assert!(!vocab.contains("card"));
+        assert_eq!(vocab.score_history("cat").len(), 1);
+        assert!(vocab.contains("cart"));
+    }
+
+    #[test]
+    fn test_restore_rejects_other_versions() {
+        let mut vocab = vocabulary(&["cat"]);
+        let mut snapshot = vocabulary(&["dog"]).to_snapshot();
+        snapshot.snapshot_version = SNAPSHOT_VERSION + 1;
+        assert!(vocab.restore_snapshot(snapshot).is_err());
+        assert!(vocab.contains("cat"));
+    }
+
+    #[test]
+    fn test_max_words() {
+        let list = |n: usize| (0..n).map(|i| format!("word{}", i)).collect::<Vec<_>>();
+        assert!(WasmVocabulary::with_max_words(list(4), 3).is_err());
+        // Duplicates don't count towards the limit
+        let mut repeated = list(3);
+        repeated.extend(list(3));
+        let mut vocab = WasmVocabulary::with_max_words(repeated, 3).unwrap();
+        assert_eq!(vocab.len(), 3);
+        let many_duplicates = vec!["cat".to_string(); 1_000_000];
+        assert_eq!(
+            WasmVocabulary::with_max_words(many_duplicates, 1)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        assert_eq!(vocab.insert_word("word0"), Ok(false));
+        assert!(vocab.insert_word("extra").is_err());
+        assert!(vocab.len() <= vocab.max_words());
+        assert_eq!(vocabulary(&["cat"]).max_words(), DEFAULT_MAX_WORDS);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut vocab = WasmVocabulary::with_max_words(words(&["cat", "dog"]), 3).unwrap();
+        let mut other = vocabulary(&["dog", "owl"]);
+        other.record_score("owl", 0.5);
+        vocab.merge_from(&other).unwrap();
+        assert_eq!(vocab.word_list(), vec!["cat", "dog", "owl"]);
+        assert_eq!(vocab.score_history("owl"), vec![0.5]);
+
+        let full = vocabulary(&["emu", "yak"]);
+        assert!(vocab.merge_from(&full).is_err());
+        assert_eq!(vocab.len(), 3);
+    }
+
+    #[test]
+    fn test_vocabulary_issues_clean() {
+        let list = words(&["cat", "dog", "owl"]);
+        let result = vocabulary_issues(&list);
+        assert!(result.is_valid);
+        assert!(result.issues.is_empty());
+        assert!(vocabulary_issues(&[]).is_valid);
+    }
+
+    #[test]
+    fn test_vocabulary_issues_each_kind() {
+        let issue_of = |list: Vec<String>| {
+            let result = vocabulary_issues(&list);
+            assert!(!result.is_valid);
+            assert_eq!(result.issues.len(), 1);
+            (result.issues[0].index, result.issues[0].issue.clone())
+        };
+        let cat = || "cat".to_string();
+        assert_eq!(
+            issue_of(vec![cat(), String::new()]),
+            (1, "empty".to_string())
+        );
+        assert_eq!(issue_of(vec![cat(), cat()]), (1, "duplicate".to_string()));
+        assert_eq!(issue_of(vec!["a".repeat(101)]), (0, "too_long".to_string()));
+        assert_eq!(
+            issue_of(vec!["c\0t".to_string()]),
+            (0, "null_byte".to_string())
+        );
+        assert!(vocabulary_issues(&["é".repeat(100)]).is_valid);
+    }
+}